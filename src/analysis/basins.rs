@@ -0,0 +1,138 @@
+use super::parallel::parallel_map;
+use super::phase_plane::marching_squares;
+use super::Segment;
+use crate::ode_solvers::{ODESolver2, RightHandSide};
+use ndarray::{Array1, Array2};
+use std::fmt;
+
+const SEPARATRIX_BISECTIONS: usize = 8;
+
+/// Where a trajectory of a two-species model ends up.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Extinction,
+    /// Competitive exclusion in favour of the first species.
+    FirstSurvives,
+    /// Competitive exclusion in favour of the second species.
+    SecondSurvives,
+    Coexistence,
+    /// Still moving, cycling or unbounded at the final time.
+    Unsettled,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 5] = [
+        Outcome::Extinction,
+        Outcome::FirstSurvives,
+        Outcome::SecondSurvives,
+        Outcome::Coexistence,
+        Outcome::Unsettled,
+    ];
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            Outcome::Extinction => "extinction",
+            Outcome::FirstSurvives => "first survives",
+            Outcome::SecondSurvives => "second survives",
+            Outcome::Coexistence => "coexistence",
+            Outcome::Unsettled => "unsettled",
+        })
+    }
+}
+
+/// The outcome of the trajectory from each node `(x[i], y[j])` of a grid over `bounds`.
+pub struct BasinMap<'a> {
+    pub bounds: [(f64, f64); 2],
+    pub labels: [&'a str; 2],
+    pub x: Array1<f64>,
+    pub y: Array1<f64>,
+    pub outcomes: Array2<Outcome>,
+    /// Boundaries between basins, placed on the grid edges by bisecting the initial condition.
+    pub separatrix: Vec<Segment>,
+}
+
+impl BasinMap<'_> {
+    /// The outcomes that occur, in the order of `Outcome::ALL`.
+    pub fn present(&self) -> Vec<Outcome> {
+        Outcome::ALL
+            .into_iter()
+            .filter(|o| self.outcomes.iter().any(|x| x == o))
+            .collect()
+    }
+}
+
+/// Classifies the end state of the planar model `rhs` from every node of a
+/// `resolution x resolution` grid over `bounds`, integrating with `n_steps` steps up to `tn`.
+/// The grid columns are shared out over the available threads. A species counts as extinct
+/// below a thousandth of its range in `bounds`, and a trajectory as settled if it moved less than
+/// that over the last tenth of the run.
+pub fn basin_map<'a, R>(
+    ode_solver: ODESolver2,
+    rhs: &R,
+    bounds: [(f64, f64); 2],
+    labels: [&'a str; 2],
+    resolution: usize,
+    tn: f64,
+    n_steps: usize,
+) -> BasinMap<'a>
+where
+    R: RightHandSide + Sync,
+{
+    let [(x_min, x_max), (y_min, y_max)] = bounds;
+    let x = Array1::linspace(x_min, x_max, resolution);
+    let y = Array1::linspace(y_min, y_max, resolution);
+    let (tol_x, tol_y) = (1e-3 * (x_max - x_min), 1e-3 * (y_max - y_min));
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let h = tn / n_steps as f64;
+    let tenth = n_steps - n_steps / 10;
+
+    let outcome = |x_0: f64, y_0: f64| {
+        let (xt, yt) = ode_solver.solve_rhs(rhs, h, n_steps, &t, x_0, y_0);
+        let (x_n, y_n) = (xt[n_steps], yt[n_steps]);
+        if !x_n.is_finite()
+            || !y_n.is_finite()
+            || (x_n - xt[tenth]).abs() > tol_x
+            || (y_n - yt[tenth]).abs() > tol_y
+        {
+            return Outcome::Unsettled;
+        }
+        match (x_n > tol_x, y_n > tol_y) {
+            (false, false) => Outcome::Extinction,
+            (true, false) => Outcome::FirstSurvives,
+            (false, true) => Outcome::SecondSurvives,
+            (true, true) => Outcome::Coexistence,
+        }
+    };
+
+    let columns: Vec<Vec<Outcome>> = parallel_map(resolution, |i| {
+        y.iter().map(|&y_0| outcome(x[i], y_0)).collect()
+    });
+
+    let outcomes = Array2::from_shape_fn((resolution, resolution), |(i, j)| columns[i][j]);
+    let separatrix = marching_squares(resolution, resolution, |a, b| {
+        if outcomes[a] == outcomes[b] {
+            return None;
+        }
+        let (mut p, mut q) = ((x[a.0], y[a.1]), (x[b.0], y[b.1]));
+        for _ in 0..SEPARATRIX_BISECTIONS {
+            let mid = (0.5 * (p.0 + q.0), 0.5 * (p.1 + q.1));
+            if outcome(mid.0, mid.1) == outcomes[a] {
+                p = mid;
+            } else {
+                q = mid;
+            }
+        }
+        Some((0.5 * (p.0 + q.0), 0.5 * (p.1 + q.1)))
+    });
+
+    BasinMap {
+        bounds,
+        labels,
+        x,
+        y,
+        outcomes,
+        separatrix,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,363 @@
+use super::Stability;
+use crate::linalg;
+use crate::ode_solvers::ODESolver;
+use ndarray::{concatenate, s, Array1, Array2, Axis};
+use std::fmt;
+
+const NEWTON_TOL: f64 = 1e-10;
+const CORRECTOR_MAX_ITERATIONS: usize = 10;
+
+#[derive(Copy, Clone)]
+pub struct ContinuationOptions {
+    /// Initial arclength step. Its sign picks the direction the parameter starts moving in.
+    pub ds: f64,
+    pub ds_min: f64,
+    pub ds_max: f64,
+    pub max_steps: usize,
+}
+
+impl Default for ContinuationOptions {
+    fn default() -> Self {
+        Self {
+            ds: 1e-2,
+            ds_min: 1e-8,
+            ds_max: 0.1,
+            max_steps: 10_000,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BifurcationKind {
+    /// Saddle-node: the branch turns back in the parameter.
+    Fold,
+    /// A complex pair of eigenvalues crosses the imaginary axis.
+    Hopf,
+}
+
+impl fmt::Display for BifurcationKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(match self {
+            BifurcationKind::Fold => "fold",
+            BifurcationKind::Hopf => "Hopf",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Bifurcation {
+    pub kind: BifurcationKind,
+    pub parameter: f64,
+    pub y: Array1<f64>,
+}
+
+/// A curve of equilibria, one row of `y` per entry of `parameter`.
+pub struct Branch {
+    pub parameter: Array1<f64>,
+    pub y: Array2<f64>,
+    pub stable: Vec<bool>,
+    pub bifurcations: Vec<Bifurcation>,
+}
+
+impl Branch {
+    /// Maximal runs of equal stability as `(parameter, y[component], stable)`. Neighbouring runs
+    /// share their end point so the curve has no gaps.
+    pub fn segments(&self, component: usize) -> Vec<(Array1<f64>, Array1<f64>, bool)> {
+        let mut segments = Vec::new();
+        let mut start = 0;
+        for i in 1..=self.stable.len() {
+            if i == self.stable.len() || self.stable[i] != self.stable[start] {
+                let end = (i + 1).min(self.stable.len());
+                segments.push((
+                    self.parameter.slice(s![start..end]).to_owned(),
+                    self.y.slice(s![start..end, component]).to_owned(),
+                    self.stable[start],
+                ));
+                start = i;
+            }
+        }
+        segments
+    }
+}
+
+/// Pseudo-arclength continuation of the equilibria of `y' = f(y, p)` from near `(y_0, p_0)`
+/// until `p` leaves `p_range`. Folds are where the parameter component of the tangent changes
+/// sign, Hopf points where the trace of a planar Jacobian does while its determinant is positive;
+/// both are located by linear interpolation between the bracketing points.
+pub fn continue_equilibria<F>(
+    f: F,
+    y_0: &Array1<f64>,
+    p_0: f64,
+    p_range: (f64, f64),
+    options: &ContinuationOptions,
+) -> Branch
+where
+    F: Fn(&Array1<f64>, f64) -> Array1<f64>,
+{
+    let n = y_0.len();
+    assert!(
+        n == 1 || n == 2,
+        "continuation classifies one- and two-dimensional models"
+    );
+    let g = |u: &Array1<f64>| f(&u.slice(s![..n]).to_owned(), u[n]);
+
+    let y_0 = correct_equilibrium(|y| f(y, p_0), y_0)
+        .expect("no equilibrium near the starting point of the continuation");
+    let mut u = concatenate![Axis(0), y_0, Array1::from_elem(1, p_0)];
+    let mut jac = linalg::jacobian(g, &u);
+    let mut e_p = Array1::zeros(n + 1);
+    e_p[n] = options.ds.signum();
+    let mut tangent = unit_tangent(&jac, &e_p);
+    let mut ds = options.ds.abs();
+
+    let mut branch = Branch {
+        parameter: Array1::zeros(0),
+        y: Array2::zeros((0, n)),
+        stable: Vec::new(),
+        bifurcations: Vec::new(),
+    };
+    let mut points = vec![u.clone()];
+    let mut stable = vec![is_stable(&jac, n)];
+
+    for _ in 0..options.max_steps {
+        let predicted = &u + &(ds * &tangent);
+        let Some((corrected, iterations)) = correct_arclength(g, &predicted, &tangent) else {
+            ds /= 2.;
+            if ds < options.ds_min {
+                break;
+            }
+            continue;
+        };
+        if corrected[n] < p_range.0 || corrected[n] > p_range.1 {
+            break;
+        }
+
+        let jac_next = linalg::jacobian(g, &corrected);
+        let tangent_next = unit_tangent(&jac_next, &tangent);
+        if tangent[n] * tangent_next[n] < 0. {
+            let s = tangent[n] / (tangent[n] - tangent_next[n]);
+            let mut fold = interpolated(BifurcationKind::Fold, &u, &corrected, s);
+            // p is quadratic in arclength near the turn, so take the vertex of the parabola
+            // with the end slopes instead of the chord
+            let step = &corrected - &u;
+            fold.parameter = u[n] + 0.5 * tangent[n] * s * step.dot(&step).sqrt();
+            branch.bifurcations.push(fold);
+        }
+        if n == 2 {
+            let (trace, det) = trace_det(&jac);
+            let (trace_next, det_next) = trace_det(&jac_next);
+            if trace * trace_next < 0. && det > 0. && det_next > 0. {
+                let s = trace / (trace - trace_next);
+                branch
+                    .bifurcations
+                    .push(interpolated(BifurcationKind::Hopf, &u, &corrected, s));
+            }
+        }
+
+        u = corrected;
+        jac = jac_next;
+        tangent = tangent_next;
+        points.push(u.clone());
+        stable.push(is_stable(&jac, n));
+        if iterations <= 3 {
+            ds = (1.5 * ds).min(options.ds_max);
+        }
+    }
+
+    branch.parameter = points.iter().map(|u| u[n]).collect();
+    branch.y = Array2::from_shape_fn((points.len(), n), |(i, j)| points[i][j]);
+    branch.stable = stable;
+    branch
+}
+
+fn correct_equilibrium<F>(f: F, y_0: &Array1<f64>) -> Option<Array1<f64>>
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    let mut y = y_0.clone();
+    for _ in 0..50 {
+        let delta = linalg::solve(&linalg::jacobian(&f, &y), &f(&y))?;
+        y = &y - &delta;
+        if delta.dot(&delta).sqrt() <= NEWTON_TOL * (1. + y.dot(&y).sqrt()) {
+            return Some(y);
+        }
+    }
+    None
+}
+
+/// Newton on `g(u) = 0` with `tangent . (u - predicted) = 0`, returning the point and the
+/// number of iterations it took.
+fn correct_arclength<G>(
+    g: G,
+    predicted: &Array1<f64>,
+    tangent: &Array1<f64>,
+) -> Option<(Array1<f64>, usize)>
+where
+    G: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    let n = predicted.len() - 1;
+    let mut u = predicted.clone();
+    for iteration in 1..=CORRECTOR_MAX_ITERATIONS {
+        let mut residual = Array1::zeros(n + 1);
+        residual.slice_mut(s![..n]).assign(&g(&u));
+        residual[n] = tangent.dot(&(&u - predicted));
+        let mut jac = Array2::zeros((n + 1, n + 1));
+        jac.slice_mut(s![..n, ..]).assign(&linalg::jacobian(&g, &u));
+        jac.row_mut(n).assign(tangent);
+        let delta = linalg::solve(&jac, &residual)?;
+        u = &u - &delta;
+        if !u.iter().all(|u_i| u_i.is_finite()) {
+            return None;
+        }
+        if delta.dot(&delta).sqrt() <= NEWTON_TOL * (1. + u.dot(&u).sqrt()) {
+            return Some((u, iteration));
+        }
+    }
+    None
+}
+
+/// Unit null vector of the `n x (n + 1)` Jacobian, oriented along `previous`.
+fn unit_tangent(jac: &Array2<f64>, previous: &Array1<f64>) -> Array1<f64> {
+    let n = jac.nrows();
+    let mut a = Array2::zeros((n + 1, n + 1));
+    a.slice_mut(s![..n, ..]).assign(jac);
+    a.row_mut(n).assign(previous);
+    let mut b = Array1::zeros(n + 1);
+    b[n] = 1.;
+    let t = linalg::solve(&a, &b).expect("singular continuation system");
+    &t / t.dot(&t).sqrt()
+}
+
+fn is_stable(jac: &Array2<f64>, n: usize) -> bool {
+    let jac_y = jac.slice(s![.., ..n]).to_owned();
+    if n == 1 {
+        jac_y[[0, 0]] < 0.
+    } else {
+        Stability::classify(&jac_y, 1e-9).is_stable()
+    }
+}
+
+fn trace_det(jac: &Array2<f64>) -> (f64, f64) {
+    (
+        jac[[0, 0]] + jac[[1, 1]],
+        jac[[0, 0]] * jac[[1, 1]] - jac[[0, 1]] * jac[[1, 0]],
+    )
+}
+
+fn interpolated(kind: BifurcationKind, u: &Array1<f64>, v: &Array1<f64>, s: f64) -> Bifurcation {
+    let n = u.len() - 1;
+    let w = u + &(s * &(v - u));
+    Bifurcation {
+        kind,
+        parameter: w[n],
+        y: w.slice(s![..n]).to_owned(),
+    }
+}
+
+/// Long-run local maxima and minima of one component, as `(parameter, value)` pairs.
+pub struct OrbitDiagram {
+    pub maxima: (Array1<f64>, Array1<f64>),
+    pub minima: (Array1<f64>, Array1<f64>),
+}
+
+/// Brute-force bifurcation diagram: for each parameter value integrates `f(t, y, p)` from `y_0`
+/// with step `h`, drops `transient` and records the local extrema of `component` over the next
+/// `record` time units. A solution that has settled, or is still creeping monotonically towards
+/// an equilibrium, contributes its final value to both.
+#[allow(clippy::too_many_arguments)]
+pub fn orbit_diagram<F>(
+    ode_solver: ODESolver,
+    f: F,
+    y_0: &Array1<f64>,
+    parameters: &Array1<f64>,
+    component: usize,
+    h: f64,
+    transient: f64,
+    record: f64,
+) -> OrbitDiagram
+where
+    F: Fn(f64, &Array1<f64>, f64) -> Array1<f64>,
+{
+    let n_steps = ((transient + record) / h).round() as usize;
+    let first_recorded = (transient / h).round() as usize;
+    let t = Array1::linspace(0., n_steps as f64 * h, n_steps + 1);
+    let (mut maxima, mut minima) = (Vec::new(), Vec::new());
+
+    for &p in parameters {
+        let y = ode_solver.solve(|t, y| f(t, y, p), h, n_steps, &t, y_0);
+        let x = y.slice(s![first_recorded.., component]).to_owned();
+        let (min, max) = x.fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), x_i| {
+            (a.min(*x_i), b.max(*x_i))
+        });
+        let n_extrema = maxima.len() + minima.len();
+        if max - min > 1e-6 * (1. + max.abs()) {
+            for i in 1..x.len() - 1 {
+                if x[i] > x[i - 1] && x[i] >= x[i + 1] {
+                    maxima.push((p, x[i]));
+                } else if x[i] < x[i - 1] && x[i] <= x[i + 1] {
+                    minima.push((p, x[i]));
+                }
+            }
+        }
+        if maxima.len() + minima.len() == n_extrema {
+            maxima.push((p, x[x.len() - 1]));
+            minima.push((p, x[x.len() - 1]));
+        }
+    }
+
+    let unzip = |points: Vec<(f64, f64)>| {
+        let (p, x): (Vec<f64>, Vec<f64>) = points.into_iter().unzip();
+        (Array1::from(p), Array1::from(x))
+    };
+    OrbitDiagram {
+        maxima: unzip(maxima),
+        minima: unzip(minima),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn harvesting_equilibria_fold_at_the_critical_harvest() {
+        // n' = n (1 - n / 10) - h has equilibria 5 +- sqrt(25 - 10 h), which meet at h = 2.5
+        let branch = continue_equilibria(
+            |n: &Array1<f64>, h| n.mapv(|n| n * (1. - n / 10.) - h),
+            &array![10.],
+            0.,
+            (0., 3.),
+            &ContinuationOptions::default(),
+        );
+        assert_eq!(branch.bifurcations.len(), 1);
+        let fold = &branch.bifurcations[0];
+        assert_eq!(fold.kind, BifurcationKind::Fold);
+        assert!((fold.parameter - 2.5).abs() < 1e-6, "{}", fold.parameter);
+        assert!((fold.y[0] - 5.).abs() < 1e-3, "{}", fold.y[0]);
+        for (i, (&h, n)) in branch.parameter.iter().zip(branch.y.column(0)).enumerate() {
+            assert!((n * (1. - n / 10.) - h).abs() < 1e-8);
+            // the upper branch is stable and the lower one unstable
+            assert_eq!(branch.stable[i], *n > 5.);
+        }
+    }
+
+    #[test]
+    fn hopf_normal_form_loses_stability_at_zero() {
+        let branch = continue_equilibria(
+            |z: &Array1<f64>, p| {
+                let (x, y) = (z[0], z[1]);
+                let r2 = x * x + y * y;
+                array![p * x - y - x * r2, x + p * y - y * r2]
+            },
+            &array![0., 0.],
+            -1.,
+            (-1., 1.),
+            &ContinuationOptions::default(),
+        );
+        assert_eq!(branch.bifurcations.len(), 1);
+        let hopf = &branch.bifurcations[0];
+        assert_eq!(hopf.kind, BifurcationKind::Hopf);
+        assert!(hopf.parameter.abs() < 1e-8, "{}", hopf.parameter);
+    }
+}
\ No newline at end of file
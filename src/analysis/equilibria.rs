@@ -0,0 +1,183 @@
+use crate::linalg;
+use crate::ode_solvers::RightHandSide;
+use ndarray::{array, Array1, Array2};
+use std::fmt;
+
+const NEWTON_TOL: f64 = 1e-10;
+const NEWTON_MAX_ITERATIONS: usize = 50;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Stability {
+    StableNode,
+    UnstableNode,
+    Saddle,
+    StableFocus,
+    UnstableFocus,
+    Centre,
+    /// A zero eigenvalue, where the linearisation does not decide the stability.
+    NonHyperbolic,
+    /// The right-hand side is not differentiable there, e.g. `N / (N + n)` at the origin, so
+    /// there is no linearisation.
+    Singular,
+}
+
+impl Stability {
+    /// Classifies a planar equilibrium from the trace and determinant of its Jacobian. `tol`
+    /// decides when the determinant or the trace counts as zero.
+    pub fn classify(jacobian: &Array2<f64>, tol: f64) -> Self {
+        if jacobian.iter().any(|a| !a.is_finite()) {
+            return Stability::Singular;
+        }
+        let trace = jacobian[[0, 0]] + jacobian[[1, 1]];
+        let det = jacobian[[0, 0]] * jacobian[[1, 1]] - jacobian[[0, 1]] * jacobian[[1, 0]];
+        let scale = jacobian
+            .iter()
+            .fold(0., |m: f64, a| m.max(a.abs()))
+            .max(1e-300);
+        if det.abs() <= tol * scale * scale {
+            Stability::NonHyperbolic
+        } else if det < 0. {
+            Stability::Saddle
+        } else if trace * trace >= 4. * det {
+            if trace < 0. {
+                Stability::StableNode
+            } else {
+                Stability::UnstableNode
+            }
+        } else if trace.abs() <= tol * scale {
+            Stability::Centre
+        } else if trace < 0. {
+            Stability::StableFocus
+        } else {
+            Stability::UnstableFocus
+        }
+    }
+
+    pub fn is_stable(self) -> bool {
+        matches!(self, Stability::StableNode | Stability::StableFocus)
+    }
+}
+
+impl fmt::Display for Stability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Stability::StableNode => "stable node",
+            Stability::UnstableNode => "unstable node",
+            Stability::Saddle => "saddle",
+            Stability::StableFocus => "stable focus",
+            Stability::UnstableFocus => "unstable focus",
+            Stability::Centre => "centre",
+            Stability::NonHyperbolic => "non-hyperbolic",
+            Stability::Singular => "singular",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Equilibrium {
+    pub y: Array1<f64>,
+    pub jacobian: Array2<f64>,
+    pub eigenvalues: [(f64, f64); 2],
+    pub stability: Stability,
+}
+
+impl Equilibrium {
+    pub fn at<R: RightHandSide>(rhs: &R, y: Array1<f64>) -> Self {
+        let jacobian = rhs.jacobian(0., &y);
+        Self {
+            eigenvalues: linalg::eigenvalues2(&jacobian),
+            stability: Stability::classify(&jacobian, 1e-9),
+            jacobian,
+            y,
+        }
+    }
+}
+
+/// Newton's method on `f(y) = 0` with the model's exact Jacobian, halving steps that do not
+/// reduce the residual. `None` if it does not converge or the Jacobian is singular.
+pub fn newton<R: RightHandSide>(rhs: &R, y_0: &Array1<f64>) -> Option<Array1<f64>> {
+    let norm = |v: &Array1<f64>| v.dot(v).sqrt();
+    let mut y = y_0.clone();
+    let mut f = rhs.f(0., &y);
+    for _ in 0..NEWTON_MAX_ITERATIONS {
+        let delta = linalg::solve(&rhs.jacobian(0., &y), &f)?;
+        let mut step = 1.;
+        let mut y_next = &y - &delta;
+        let mut f_next = rhs.f(0., &y_next);
+        while norm(&f_next) > norm(&f) && step > 1e-3 {
+            step /= 2.;
+            y_next = &y - &(step * &delta);
+            f_next = rhs.f(0., &y_next);
+        }
+        let converged = norm(&(&y_next - &y)) <= NEWTON_TOL * (1. + norm(&y_next));
+        y = y_next;
+        f = f_next;
+        if !y.iter().all(|y_i| y_i.is_finite()) {
+            return None;
+        }
+        if converged {
+            return Some(y);
+        }
+    }
+    None
+}
+
+/// All equilibria of an autonomous planar model found by Newton from a
+/// `starts_per_axis x starts_per_axis` grid over `bounds`, with duplicates merged and the ones
+/// outside the box dropped. Sorted by the first then the second coordinate.
+pub fn find_equilibria<R: RightHandSide>(
+    rhs: &R,
+    bounds: [(f64, f64); 2],
+    starts_per_axis: usize,
+) -> Vec<Equilibrium> {
+    let [(x_min, x_max), (y_min, y_max)] = bounds;
+    let diagonal = (x_max - x_min).hypot(y_max - y_min);
+    let inside = |y: &Array1<f64>| {
+        let tol = 1e-9 * diagonal;
+        y[0] >= x_min - tol && y[0] <= x_max + tol && y[1] >= y_min - tol && y[1] <= y_max + tol
+    };
+
+    let mut roots: Vec<Array1<f64>> = Vec::new();
+    for x_0 in Array1::linspace(x_min, x_max, starts_per_axis) {
+        for y_0 in Array1::linspace(y_min, y_max, starts_per_axis) {
+            let Some(root) = newton(rhs, &array![x_0, y_0]) else {
+                continue;
+            };
+            let duplicate = roots
+                .iter()
+                .any(|r| (r[0] - root[0]).hypot(r[1] - root[1]) <= 1e-6 * diagonal);
+            if inside(&root) && !duplicate {
+                roots.push(root);
+            }
+        }
+    }
+    roots.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    roots
+        .into_iter()
+        .map(|y| Equilibrium::at(rhs, y.mapv(|y_i| if y_i.abs() < 1e-9 { 0. } else { y_i })))
+        .collect()
+}
+
+pub fn print_equilibrium_table(model: &str, species: [&str; 2], equilibria: &[Equilibrium]) {
+    println!("Equilibria of {}", model);
+    println!(
+        "{:>14} {:>14} {:>30} {:>30}  stability",
+        species[0], species[1], "eigenvalue 1", "eigenvalue 2"
+    );
+    // a singular equilibrium has no linearisation, so no eigenvalues to show
+    let complex = |e: &Equilibrium, (re, im): (f64, f64)| match e.stability {
+        Stability::Singular => "-".to_string(),
+        _ => format!("{:.6} {:+.6}i", re, im),
+    };
+    for e in equilibria {
+        println!(
+            "{:>14.6} {:>14.6} {:>30} {:>30}  {}",
+            e.y[0],
+            e.y[1],
+            complex(e, e.eigenvalues[0]),
+            complex(e, e.eigenvalues[1]),
+            e.stability
+        );
+    }
+}
\ No newline at end of file
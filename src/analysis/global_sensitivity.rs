@@ -0,0 +1,352 @@
+use super::parallel::parallel_map;
+use ndarray::{s, Array1, Array2};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Parameters varied by a global analysis, each uniform over its `(lower, upper)` range.
+pub type ParameterRanges = [(&'static str, (f64, f64))];
+
+#[derive(Copy, Clone)]
+pub struct SobolOptions {
+    /// Base sample size `N`; the model is run `N (d + 2)` times for `d` parameters.
+    pub n_samples: usize,
+    /// Bootstrap resamples for the confidence intervals.
+    pub n_bootstrap: usize,
+    pub seed: u64,
+}
+
+impl Default for SobolOptions {
+    fn default() -> Self {
+        Self {
+            n_samples: 1024,
+            n_bootstrap: 200,
+            seed: 1,
+        }
+    }
+}
+
+/// First and total-order Sobol indices of one model output, with bootstrap 95% confidence
+/// half-widths.
+pub struct SobolIndices {
+    pub names: Vec<&'static str>,
+    pub first_order: Array1<f64>,
+    pub total_order: Array1<f64>,
+    pub first_order_confidence: Array1<f64>,
+    pub total_order_confidence: Array1<f64>,
+    pub variance: f64,
+}
+
+impl SobolIndices {
+    pub fn print_summary(&self, title: &str) {
+        println!("{}: output variance {:.6e}", title, self.variance);
+        println!(
+            "{:>14} {:>22} {:>22}",
+            "parameter", "first order", "total order"
+        );
+        for (k, name) in self.names.iter().enumerate() {
+            println!(
+                "{:>14} {:>22} {:>22}",
+                name,
+                format!(
+                    "{:.4} ± {:.4}",
+                    self.first_order[k], self.first_order_confidence[k]
+                ),
+                format!(
+                    "{:.4} ± {:.4}",
+                    self.total_order[k], self.total_order_confidence[k]
+                )
+            );
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct MorrisOptions {
+    /// Number of one-at-a-time trajectories, each costing `d + 1` model runs.
+    pub n_trajectories: usize,
+    /// Number of grid levels per parameter, even so the steps are symmetric.
+    pub levels: usize,
+    pub seed: u64,
+}
+
+impl Default for MorrisOptions {
+    fn default() -> Self {
+        Self {
+            n_trajectories: 50,
+            levels: 4,
+            seed: 1,
+        }
+    }
+}
+
+/// Morris elementary effect statistics of one model output, per unit fraction of each
+/// parameter's range. `mu_star` ranks importance and `sigma` flags interactions or nonlinearity.
+pub struct MorrisEffects {
+    pub names: Vec<&'static str>,
+    pub mu: Array1<f64>,
+    pub mu_star: Array1<f64>,
+    pub sigma: Array1<f64>,
+}
+
+impl MorrisEffects {
+    pub fn print_summary(&self, title: &str) {
+        println!("{}", title);
+        println!(
+            "{:>14} {:>14} {:>14} {:>14}",
+            "parameter", "mu", "mu*", "sigma"
+        );
+        for (k, name) in self.names.iter().enumerate() {
+            println!(
+                "{:>14} {:>14.6} {:>14.6} {:>14.6}",
+                name, self.mu[k], self.mu_star[k], self.sigma[k]
+            );
+        }
+    }
+}
+
+/// Runs `model` at every row of `points`, given as fractions of each range, sharing the rows out
+/// over the available threads. One row of the result per point, one column per model output.
+fn evaluate<F>(model: &F, parameters: &ParameterRanges, points: &Array2<f64>) -> Array2<f64>
+where
+    F: Fn(&Array1<f64>) -> Array1<f64> + Sync,
+{
+    let n = points.nrows();
+    let outputs = parallel_map(n, |i| {
+        let p = Array1::from_shape_fn(parameters.len(), |j| {
+            let (lower, upper) = parameters[j].1;
+            lower + points[[i, j]] * (upper - lower)
+        });
+        model(&p)
+    });
+
+    let m = outputs[0].len();
+    let y = Array2::from_shape_fn((n, m), |(i, k)| outputs[i][k]);
+    assert!(
+        y.iter().all(|y| y.is_finite()),
+        "the model output is not finite for some parameter values"
+    );
+    y
+}
+
+/// Sample variance.
+fn variance(x: &[f64]) -> f64 {
+    let n = x.len() as f64;
+    let mean = x.iter().sum::<f64>() / n;
+    x.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.).max(1.)
+}
+
+/// Half-width of the normal 95% confidence interval from bootstrap estimates.
+fn half_width(resampled: &[Array1<f64>]) -> Array1<f64> {
+    Array1::from_shape_fn(resampled[0].len(), |i| {
+        let x: Vec<f64> = resampled.iter().map(|r| r[i]).collect();
+        1.96 * variance(&x).sqrt()
+    })
+}
+
+/// Saltelli's estimators over the base rows `rows`: `S_i = E[(f_B - f_0) (f_ABi - f_A)] / V` and
+/// Jansen's `S_Ti = E[(f_A - f_ABi)^2] / 2V`, where `f_0` is the mean output.
+fn sobol_estimates(
+    f_a: &[f64],
+    f_b: &[f64],
+    f_ab: &[Vec<f64>],
+    rows: &[usize],
+) -> (Array1<f64>, Array1<f64>, f64) {
+    let n = rows.len() as f64;
+    let pooled: Vec<f64> = rows.iter().flat_map(|&r| [f_a[r], f_b[r]]).collect();
+    let v = variance(&pooled);
+    // centring f_B leaves the estimate unbiased and cuts its variance when the mean is large
+    let f_0 = pooled.iter().sum::<f64>() / pooled.len() as f64;
+    let first = f_ab
+        .iter()
+        .map(|f_ab_i| {
+            rows.iter()
+                .map(|&r| (f_b[r] - f_0) * (f_ab_i[r] - f_a[r]))
+                .sum::<f64>()
+                / n
+                / v
+        })
+        .collect();
+    let total = f_ab
+        .iter()
+        .map(|f_ab_i| {
+            rows.iter()
+                .map(|&r| (f_a[r] - f_ab_i[r]).powi(2))
+                .sum::<f64>()
+                / (2. * n * v)
+        })
+        .collect();
+    (first, total, v)
+}
+
+/// First and total-order Sobol indices of every output of `model` by Saltelli's sampling scheme:
+/// two independent uniform samples `A` and `B` over `parameters`, and for each parameter the
+/// matrix `A` with that column taken from `B`. Returns one set of indices per model output.
+pub fn sobol_indices<F>(
+    model: F,
+    parameters: &ParameterRanges,
+    options: &SobolOptions,
+) -> Vec<SobolIndices>
+where
+    F: Fn(&Array1<f64>) -> Array1<f64> + Sync,
+{
+    let (n, d) = (options.n_samples, parameters.len());
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let a = Array2::from_shape_fn((n, d), |_| rng.gen::<f64>());
+    let b = Array2::from_shape_fn((n, d), |_| rng.gen::<f64>());
+
+    let mut points = Array2::zeros((n * (d + 2), d));
+    points.slice_mut(s![..n, ..]).assign(&a);
+    points.slice_mut(s![n..2 * n, ..]).assign(&b);
+    for i in 0..d {
+        let mut ab_i = a.clone();
+        ab_i.column_mut(i).assign(&b.column(i));
+        points
+            .slice_mut(s![(i + 2) * n..(i + 3) * n, ..])
+            .assign(&ab_i);
+    }
+    let y = evaluate(&model, parameters, &points);
+
+    let all_rows: Vec<usize> = (0..n).collect();
+    y.columns()
+        .into_iter()
+        .map(|y_k| {
+            let block = |m: usize| y_k.slice(s![m * n..(m + 1) * n]).to_vec();
+            let (f_a, f_b) = (block(0), block(1));
+            let f_ab: Vec<Vec<f64>> = (0..d).map(|i| block(i + 2)).collect();
+            let (first_order, total_order, v) = sobol_estimates(&f_a, &f_b, &f_ab, &all_rows);
+
+            let (mut first_resampled, mut total_resampled) = (Vec::new(), Vec::new());
+            for _ in 0..options.n_bootstrap {
+                let rows: Vec<usize> = (0..n).map(|_| rng.gen_range(0..n)).collect();
+                let (first, total, _) = sobol_estimates(&f_a, &f_b, &f_ab, &rows);
+                first_resampled.push(first);
+                total_resampled.push(total);
+            }
+
+            SobolIndices {
+                names: parameters.iter().map(|(name, _)| *name).collect(),
+                first_order_confidence: half_width(&first_resampled),
+                total_order_confidence: half_width(&total_resampled),
+                first_order,
+                total_order,
+                variance: v,
+            }
+        })
+        .collect()
+}
+
+/// Morris screening of every output of `model`. Each trajectory starts at a random node of a
+/// `levels`-point grid over the unit-scaled `parameters` and moves one parameter at a time, in
+/// random order, by `levels / (2 (levels - 1))` of its range. Returns one set of statistics per
+/// model output.
+pub fn morris_screening<F>(
+    model: F,
+    parameters: &ParameterRanges,
+    options: &MorrisOptions,
+) -> Vec<MorrisEffects>
+where
+    F: Fn(&Array1<f64>) -> Array1<f64> + Sync,
+{
+    let (r, d, p) = (options.n_trajectories, parameters.len(), options.levels);
+    assert!(p >= 2, "Morris screening needs at least two levels");
+    let delta = p as f64 / (2. * (p - 1) as f64);
+    let mut rng = StdRng::seed_from_u64(options.seed);
+
+    // the parameter moved at each step and the signed step taken
+    let mut steps = Vec::with_capacity(r * d);
+    let mut points = Array2::zeros((r * (d + 1), d));
+    for trajectory in 0..r {
+        let mut x: Array1<f64> = (0..d)
+            .map(|_| rng.gen_range(0..p) as f64 / (p - 1) as f64)
+            .collect();
+        let mut order: Vec<usize> = (0..d).collect();
+        order.shuffle(&mut rng);
+        points.row_mut(trajectory * (d + 1)).assign(&x);
+        for (step, &i) in order.iter().enumerate() {
+            let signed = if x[i] + delta <= 1. + 1e-12 {
+                delta
+            } else {
+                -delta
+            };
+            x[i] += signed;
+            points.row_mut(trajectory * (d + 1) + step + 1).assign(&x);
+            steps.push((i, signed));
+        }
+    }
+    let y = evaluate(&model, parameters, &points);
+
+    y.columns()
+        .into_iter()
+        .map(|y_k| {
+            let mut effects = vec![Vec::with_capacity(r); d];
+            for trajectory in 0..r {
+                for step in 0..d {
+                    let (i, signed) = steps[trajectory * d + step];
+                    let row = trajectory * (d + 1) + step;
+                    effects[i].push((y_k[row + 1] - y_k[row]) / signed);
+                }
+            }
+            let mean = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+            MorrisEffects {
+                names: parameters.iter().map(|(name, _)| *name).collect(),
+                mu: effects.iter().map(|e| mean(e)).collect(),
+                mu_star: effects
+                    .iter()
+                    .map(|e| e.iter().map(|x| x.abs()).sum::<f64>() / e.len() as f64)
+                    .collect(),
+                sigma: effects.iter().map(|e| variance(e).sqrt()).collect(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn sobol_indices_match_the_ishigami_function() {
+        // Ishigami and Homma's test function with a = 7, b = 0.1 and its analytic indices
+        let ishigami = |x: &Array1<f64>| {
+            array![x[0].sin() + 7. * x[1].sin().powi(2) + 0.1 * x[2].powi(4) * x[0].sin()]
+        };
+        let parameters = [("x1", (-PI, PI)), ("x2", (-PI, PI)), ("x3", (-PI, PI))];
+        let options = SobolOptions {
+            n_samples: 8192,
+            n_bootstrap: 20,
+            seed: 1,
+        };
+        let indices = &sobol_indices(ishigami, &parameters, &options)[0];
+        let first_order = [0.3139, 0.4424, 0.];
+        let total_order = [0.5576, 0.4424, 0.2437];
+        for i in 0..3 {
+            assert!(
+                (indices.first_order[i] - first_order[i]).abs() < 0.05,
+                "{}",
+                indices.first_order
+            );
+            assert!(
+                (indices.total_order[i] - total_order[i]).abs() < 0.05,
+                "{}",
+                indices.total_order
+            );
+        }
+        assert!((indices.variance - 13.845).abs() < 0.5);
+        assert!(indices.first_order_confidence.iter().all(|c| *c < 0.1));
+    }
+
+    #[test]
+    fn morris_effects_of_a_linear_model_are_its_slopes() {
+        // elementary effects are per unit fraction of the range, so slope times range
+        let linear = |x: &Array1<f64>| array![2. * x[0] - 3. * x[1]];
+        let parameters = [("a", (0., 1.)), ("b", (10., 20.))];
+        let effects = &morris_screening(linear, &parameters, &MorrisOptions::default())[0];
+        assert!((effects.mu[0] - 2.).abs() < 1e-9);
+        assert!((effects.mu[1] + 30.).abs() < 1e-9);
+        assert!((effects.mu_star[1] - 30.).abs() < 1e-9);
+        assert!(effects.sigma.iter().all(|s| *s < 1e-9));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,284 @@
+use crate::linalg;
+use crate::ode_solvers::ODESolver;
+use ndarray::{concatenate, s, Array1, Array2, Axis};
+use std::f64::consts::TAU;
+
+const SHOOTING_TOL: f64 = 1e-10;
+const SHOOTING_MAX_ITERATIONS: usize = 30;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PeriodMethod {
+    /// Mean spacing of successive maxima.
+    Peaks,
+    /// Mean time between upward crossings of the signal's mean, a Poincaré section in one
+    /// coordinate.
+    PoincareReturn,
+    /// Lag of the first maximum of the autocorrelation after it has gone negative.
+    Autocorrelation,
+}
+
+/// Local maxima of `x` as `(t, x)`, placed by a parabola through each sample maximum and its
+/// neighbours.
+pub fn peaks(t: &Array1<f64>, x: &Array1<f64>) -> Vec<(f64, f64)> {
+    (1..x.len().saturating_sub(1))
+        .filter(|&i| x[i] > x[i - 1] && x[i] >= x[i + 1])
+        .map(|i| {
+            let (a, b, c) = (x[i - 1], x[i], x[i + 1]);
+            let curvature = a - 2. * b + c;
+            if curvature == 0. {
+                return (t[i], b);
+            }
+            let offset = 0.5 * (a - c) / curvature;
+            let dt = if offset < 0. {
+                t[i] - t[i - 1]
+            } else {
+                t[i + 1] - t[i]
+            };
+            (t[i] + offset * dt, b - 0.25 * (a - c) * offset)
+        })
+        .collect()
+}
+
+pub fn troughs(t: &Array1<f64>, x: &Array1<f64>) -> Vec<(f64, f64)> {
+    peaks(t, &-x).into_iter().map(|(t, x)| (t, -x)).collect()
+}
+
+/// Period of an oscillating signal, `None` if it does not complete two cycles. The
+/// autocorrelation needs uniformly spaced `t`.
+pub fn period(t: &Array1<f64>, x: &Array1<f64>, method: PeriodMethod) -> Option<f64> {
+    let mean_spacing = |times: &[f64]| {
+        if times.len() < 3 {
+            None
+        } else {
+            Some((times[times.len() - 1] - times[0]) / (times.len() - 1) as f64)
+        }
+    };
+    match method {
+        PeriodMethod::Peaks => {
+            let times: Vec<f64> = peaks(t, x).iter().map(|(t, _)| *t).collect();
+            mean_spacing(&times)
+        }
+        PeriodMethod::PoincareReturn => {
+            let level = x.mean().unwrap();
+            let times: Vec<f64> = (1..x.len())
+                .filter(|&i| x[i - 1] < level && x[i] >= level)
+                .map(|i| t[i - 1] + (level - x[i - 1]) / (x[i] - x[i - 1]) * (t[i] - t[i - 1]))
+                .collect();
+            mean_spacing(&times)
+        }
+        PeriodMethod::Autocorrelation => {
+            let h = t[1] - t[0];
+            assert!(
+                t.windows(2)
+                    .into_iter()
+                    .all(|w| ((w[1] - w[0]) - h).abs() <= 1e-9 * h.abs()),
+                "autocorrelation needs uniformly spaced samples"
+            );
+            let r = autocorrelation(x, x.len() / 2);
+            let negative = r.iter().position(|r_k| *r_k < 0.)?;
+            let k = (negative + 1..r.len() - 1).find(|&k| r[k] > r[k - 1] && r[k] >= r[k + 1])?;
+            let curvature = r[k - 1] - 2. * r[k] + r[k + 1];
+            let offset = if curvature == 0. {
+                0.
+            } else {
+                0.5 * (r[k - 1] - r[k + 1]) / curvature
+            };
+            Some((k as f64 + offset) * h)
+        }
+    }
+}
+
+/// Autocorrelation of `x` about its mean for lags `0..max_lag`, each lag averaged over its own
+/// overlap so the estimate does not taper and pull the maxima towards zero lag.
+fn autocorrelation(x: &Array1<f64>, max_lag: usize) -> Array1<f64> {
+    let centred = x - x.mean().unwrap();
+    let variance = centred.dot(&centred) / x.len() as f64;
+    Array1::from_shape_fn(max_lag, |k| {
+        centred
+            .slice(s![..x.len() - k])
+            .dot(&centred.slice(s![k..]))
+            / ((x.len() - k) as f64 * variance)
+    })
+}
+
+/// A sustained oscillation measured on a solved trajectory.
+#[derive(Clone, Debug)]
+pub struct Oscillation {
+    pub period: f64,
+    /// Half the mean peak-to-trough height of each component.
+    pub amplitude: Array1<f64>,
+    pub mean: Array1<f64>,
+    /// How long after a peak of the first component each component peaks, in `[0, period)`.
+    pub phase_lag: Array1<f64>,
+}
+
+impl Oscillation {
+    /// Measures the oscillation of the columns of `y`, with the period of the first one by
+    /// `method`. `None` unless the first component completes two cycles and its peak heights
+    /// stay within `tol` of the amplitude, so decaying or growing oscillations are rejected.
+    /// Drop any transient from `y` first.
+    pub fn detect(
+        t: &Array1<f64>,
+        y: &Array2<f64>,
+        method: PeriodMethod,
+        tol: f64,
+    ) -> Option<Self> {
+        let period = period(t, &y.column(0).to_owned(), method)?;
+        let mut amplitude = Array1::zeros(y.ncols());
+        let mut phase_lag = Array1::zeros(y.ncols());
+        let leading_peaks = peaks(t, &y.column(0).to_owned());
+
+        for (j, x) in y.columns().into_iter().enumerate() {
+            let x = x.to_owned();
+            let (highs, lows) = (peaks(t, &x), troughs(t, &x));
+            if highs.is_empty() || lows.is_empty() {
+                return None;
+            }
+            let mean_of = |v: &[(f64, f64)]| v.iter().map(|(_, x)| x).sum::<f64>() / v.len() as f64;
+            amplitude[j] = 0.5 * (mean_of(&highs) - mean_of(&lows));
+            if j == 0 {
+                let spread = highs.iter().map(|(_, x)| *x).fold(0., f64::max)
+                    - highs.iter().map(|(_, x)| *x).fold(f64::INFINITY, f64::min);
+                if spread > tol * amplitude[0] {
+                    return None;
+                }
+            } else {
+                // circular mean, so lags either side of a whole period do not cancel
+                let (sin, cos) = leading_peaks
+                    .iter()
+                    .filter_map(|(t_0, _)| {
+                        let (t_j, _) = highs.iter().find(|(t_j, _)| t_j >= t_0)?;
+                        Some((TAU * (t_j - t_0) / period).sin_cos())
+                    })
+                    .fold((0., 0.), |(s, c), (s_i, c_i)| (s + s_i, c + c_i));
+                phase_lag[j] = sin.atan2(cos).rem_euclid(TAU) * period / TAU;
+            }
+        }
+
+        Some(Self {
+            period,
+            amplitude,
+            mean: y.mean_axis(Axis(0)).unwrap(),
+            phase_lag,
+        })
+    }
+}
+
+/// A periodic orbit through `y_0` found by shooting.
+#[derive(Clone, Debug)]
+pub struct LimitCycle {
+    pub y_0: Array1<f64>,
+    pub period: f64,
+    /// `|y(period) - y_0|` after the last iteration.
+    pub residual: f64,
+}
+
+/// Refines a guessed periodic orbit of the autonomous `y' = f(y)` by Newton shooting on
+/// `y(T; y_0) - y_0 = 0`, with `y_0[section]` held at its guessed value as the phase
+/// condition. Each shot takes `n_steps` fixed steps of `ode_solver`. The Newton steps solve damped
+/// normal equations, so a family of closed orbits around a centre, where the system is
+/// singular, settles on the nearby member instead of failing. `None` if the Newton steps do not
+/// settle within `SHOOTING_MAX_ITERATIONS`.
+pub fn refine_cycle<F>(
+    ode_solver: ODESolver,
+    f: F,
+    y_0: &Array1<f64>,
+    period: f64,
+    section: usize,
+    n_steps: usize,
+) -> Option<LimitCycle>
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    let n = y_0.len();
+    let anchor = y_0[section];
+    let flow = |y: &Array1<f64>, period: f64| {
+        let t = Array1::linspace(0., period, n_steps + 1);
+        let y_t = ode_solver.solve(|_, y| f(y), period / n_steps as f64, n_steps, &t, y);
+        y_t.row(n_steps).to_owned()
+    };
+    let residual = |u: &Array1<f64>| {
+        let y = u.slice(s![..n]).to_owned();
+        let mut r = Array1::zeros(n + 1);
+        r.slice_mut(s![..n]).assign(&(flow(&y, u[n]) - &y));
+        r[n] = y[section] - anchor;
+        r
+    };
+
+    let mut u = concatenate![Axis(0), y_0.clone(), Array1::from_elem(1, period)];
+    for _ in 0..SHOOTING_MAX_ITERATIONS {
+        let r = residual(&u);
+        let jac = linalg::jacobian(residual, &u);
+        let jac_t = jac.t();
+        let mut normal = jac_t.dot(&jac);
+        let damping = 1e-12 * normal.diag().iter().fold(0., |m: f64, d| m.max(*d));
+        normal.diag_mut().mapv_inplace(|d| d + damping);
+        let delta = linalg::solve(&normal, &jac_t.dot(&r))?;
+        u = &u - &delta;
+        if !u.iter().all(|u_i| u_i.is_finite()) || u[n] <= 0. {
+            return None;
+        }
+        if delta.dot(&delta).sqrt() <= SHOOTING_TOL * (1. + u.dot(&u).sqrt()) {
+            let r = residual(&u).slice(s![..n]).to_owned();
+            return Some(LimitCycle {
+                y_0: u.slice(s![..n]).to_owned(),
+                period: u[n],
+                residual: r.dot(&r).sqrt(),
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+    use std::f64::consts::PI;
+
+    /// `r' = r (1 - r^2)`, `theta' = 1` in Cartesian coordinates, with the unit circle as its
+    /// limit cycle of period `2 pi`.
+    fn hopf_normal_form(y: &Array1<f64>) -> Array1<f64> {
+        let growth = 1. - y.dot(y);
+        array![growth * y[0] - y[1], growth * y[1] + y[0]]
+    }
+
+    #[test]
+    fn shooting_finds_the_unit_circle() {
+        let cycle = refine_cycle(
+            ODESolver::new(SolverMethod::DormandPrince54),
+            hopf_normal_form,
+            &array![1.3, 0.],
+            5.5,
+            1,
+            400,
+        )
+        .expect("the shooting should converge");
+        assert!((cycle.y_0[0] - 1.).abs() < 1e-8, "{}", cycle.y_0);
+        assert!(cycle.y_0[1].abs() < 1e-12);
+        assert!((cycle.period - 2. * PI).abs() < 1e-8, "{}", cycle.period);
+        assert!(cycle.residual < 1e-8);
+    }
+
+    #[test]
+    fn period_and_amplitude_of_a_sine() {
+        let t = Array1::linspace(0., 20., 2001);
+        let y = Array2::from_shape_fn((t.len(), 2), |(i, j)| {
+            2. + (1. + j as f64) * (2. * PI * t[i] / 3. - j as f64).sin()
+        });
+        for method in [
+            PeriodMethod::Peaks,
+            PeriodMethod::PoincareReturn,
+            PeriodMethod::Autocorrelation,
+        ] {
+            let period = period(&t, &y.column(0).to_owned(), method).unwrap();
+            assert!((period - 3.).abs() < 1e-2, "{:?}: {}", method, period);
+        }
+        let oscillation = Oscillation::detect(&t, &y, PeriodMethod::Peaks, 1e-3).unwrap();
+        assert!((oscillation.amplitude[0] - 1.).abs() < 1e-4);
+        assert!((oscillation.amplitude[1] - 2.).abs() < 1e-4);
+        // the second component lags by a phase of one radian
+        assert!((oscillation.phase_lag[1] - 3. / (2. * PI)).abs() < 1e-2);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,218 @@
+use crate::linalg;
+use crate::ode_solvers::{ODESolver, Stepper};
+use ndarray::{s, Array1, Array2};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+
+#[derive(Copy, Clone)]
+pub struct LyapunovOptions {
+    pub h: f64,
+    /// Steps discarded before measuring, to settle onto the attractor.
+    pub transient_steps: usize,
+    pub n_steps: usize,
+    /// Steps between renormalisations of the perturbations.
+    pub renormalise_every: usize,
+}
+
+impl Default for LyapunovOptions {
+    fn default() -> Self {
+        Self {
+            h: 0.01,
+            transient_steps: 10_000,
+            n_steps: 100_000,
+            renormalise_every: 10,
+        }
+    }
+}
+
+/// Largest Lyapunov exponent of `y' = f(t, y)` by Benettin's method: a neighbour a tiny
+/// distance away is followed alongside the reference solution and pulled back to that distance
+/// every `renormalise_every` steps, averaging the logarithmic growth in between.
+pub fn largest_lyapunov_exponent<F>(
+    ode_solver: ODESolver,
+    f: F,
+    t_0: f64,
+    y_0: &Array1<f64>,
+    options: &LyapunovOptions,
+) -> f64
+where
+    F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+{
+    let h = options.h;
+    let jac = |t: f64, y: &Array1<f64>| linalg::jacobian(|y| f(t, y), y);
+    let stepper = Stepper::new(ode_solver, &f, &jac, h, t_0, y_0);
+
+    let (mut t, mut y) = (t_0, y_0.clone());
+    for _ in 0..options.transient_steps {
+        y = stepper.one_step(t, &y, h);
+        t += h;
+    }
+
+    let d_0 = 1e-8 * (1. + y.dot(&y).sqrt());
+    let mut neighbour = &y + &Array1::from_elem(y.len(), d_0 / (y.len() as f64).sqrt());
+    let mut log_growth = 0.;
+    for i in 1..=options.n_steps {
+        neighbour = stepper.one_step(t, &neighbour, h);
+        y = stepper.one_step(t, &y, h);
+        t += h;
+        if i % options.renormalise_every == 0 || i == options.n_steps {
+            let delta = &neighbour - &y;
+            let d = delta.dot(&delta).sqrt();
+            log_growth += (d / d_0).ln();
+            neighbour = &y + &(delta * (d_0 / d));
+        }
+    }
+    log_growth / (options.n_steps as f64 * h)
+}
+
+/// All Lyapunov exponents of `y' = f(t, y)`, largest first, from the variational equations
+/// `Phi' = J Phi` integrated with the solution and re-orthonormalised by QR every
+/// `renormalise_every` steps. The Jacobian is by finite differences.
+pub fn lyapunov_spectrum<F>(
+    ode_solver: ODESolver,
+    f: F,
+    t_0: f64,
+    y_0: &Array1<f64>,
+    options: &LyapunovOptions,
+) -> Array1<f64>
+where
+    F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+{
+    let (n, h) = (y_0.len(), options.h);
+    // the state followed by the rows of Phi
+    let variational = |t: f64, u: &Array1<f64>| {
+        let y = u.slice(s![..n]).to_owned();
+        let phi = u.slice(s![n..]).into_shape((n, n)).unwrap();
+        let mut du = Array1::zeros(n + n * n);
+        du.slice_mut(s![..n]).assign(&f(t, &y));
+        let j_phi = linalg::jacobian(|y| f(t, y), &y).dot(&phi);
+        du.slice_mut(s![n..])
+            .assign(&Array1::from_iter(j_phi.iter().cloned()));
+        du
+    };
+    let jac = |t: f64, u: &Array1<f64>| linalg::jacobian(|u| variational(t, u), u);
+    let jac_y = |t: f64, y: &Array1<f64>| linalg::jacobian(|y| f(t, y), y);
+
+    let (mut t, mut y) = (t_0, y_0.clone());
+    let state_stepper = Stepper::new(ode_solver, &f, &jac_y, h, t_0, y_0);
+    for _ in 0..options.transient_steps {
+        y = state_stepper.one_step(t, &y, h);
+        t += h;
+    }
+
+    let mut u = Array1::zeros(n + n * n);
+    u.slice_mut(s![..n]).assign(&y);
+    u.slice_mut(s![n..])
+        .assign(&Array1::from_iter(Array2::<f64>::eye(n).iter().cloned()));
+    let stepper = Stepper::new(ode_solver, &variational, &jac, h, t, &u);
+
+    let mut log_growth = Array1::zeros(n);
+    for i in 1..=options.n_steps {
+        u = stepper.one_step(t, &u, h);
+        t += h;
+        if i % options.renormalise_every == 0 || i == options.n_steps {
+            let phi = u.slice(s![n..]).into_shape((n, n)).unwrap().to_owned();
+            let (q, r) = linalg::qr(&phi);
+            log_growth += &r.diag().mapv(f64::ln);
+            u.slice_mut(s![n..])
+                .assign(&Array1::from_iter(q.iter().cloned()));
+        }
+    }
+
+    let mut exponents = (log_growth / (options.n_steps as f64 * h)).to_vec();
+    exponents.sort_by(|a, b| b.total_cmp(a));
+    Array1::from(exponents)
+}
+
+/// Gottwald and Melbourne's 0-1 test: the median over `n_c` random frequencies of the
+/// correlation between lag and mean square displacement of the translation variables. Near 0
+/// for regular dynamics, near 1 for chaos. Sample `x` coarsely, at intervals comparable to its
+/// natural period; oversampled data reads as regular.
+pub fn zero_one_test(x: &Array1<f64>, n_c: usize, seed: u64) -> f64 {
+    let n = x.len();
+    let n_cut = n / 10;
+    let mean = x.mean().unwrap();
+    let lags = Array1::from_iter((1..=n_cut).map(|lag| lag as f64));
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut k: Vec<f64> = (0..n_c)
+        .map(|_| {
+            let c = rng.gen_range(PI / 5.0..4. * PI / 5.);
+            let (mut p, mut q) = (Array1::zeros(n), Array1::zeros(n));
+            let (mut p_j, mut q_j) = (0., 0.);
+            for j in 0..n {
+                p_j += x[j] * ((j + 1) as f64 * c).cos();
+                q_j += x[j] * ((j + 1) as f64 * c).sin();
+                p[j] = p_j;
+                q[j] = q_j;
+            }
+            // mean square displacement, less its oscillating term so the correlation settles
+            let d = lags.mapv(|lag| {
+                let lag_i = lag as usize;
+                let m = (0..n - lag_i)
+                    .map(|j| (p[j + lag_i] - p[j]).powi(2) + (q[j + lag_i] - q[j]).powi(2))
+                    .sum::<f64>()
+                    / (n - lag_i) as f64;
+                m - mean * mean * (1. - (lag * c).cos()) / (1. - c.cos())
+            });
+            correlation(&lags, &d)
+        })
+        .collect();
+    k.sort_by(|a, b| a.total_cmp(b));
+    k[k.len() / 2]
+}
+
+fn correlation(a: &Array1<f64>, b: &Array1<f64>) -> f64 {
+    let (a, b) = (a - a.mean().unwrap(), b - b.mean().unwrap());
+    a.dot(&b) / (a.dot(&a) * b.dot(&b)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+
+    fn decoupled_decay(_: f64, y: &Array1<f64>) -> Array1<f64> {
+        array![-y[0], -3. * y[1]]
+    }
+
+    fn logistic_map(r: f64, n: usize) -> Array1<f64> {
+        let mut x = 0.3;
+        // skip the transient so a periodic orbit is sampled on the orbit
+        for _ in 0..1000 {
+            x = r * x * (1. - x);
+        }
+        Array1::from_iter((0..n).map(|_| {
+            x = r * x * (1. - x);
+            x
+        }))
+    }
+
+    #[test]
+    fn exponents_of_a_linear_system_are_its_eigenvalues() {
+        let options = LyapunovOptions {
+            h: 0.01,
+            transient_steps: 0,
+            n_steps: 10_000,
+            renormalise_every: 10,
+        };
+        let solver = ODESolver::new(SolverMethod::Classic4);
+        let y_0 = array![1., 1.];
+        let spectrum = lyapunov_spectrum(solver, decoupled_decay, 0., &y_0, &options);
+        assert!((spectrum - array![-1., -3.]).iter().all(|e| e.abs() < 1e-6));
+        // the neighbour starts with half its offset along the faster direction, which costs
+        // ln(sqrt 2) over the whole run
+        let largest = largest_lyapunov_exponent(solver, decoupled_decay, 0., &y_0, &options);
+        assert!((largest + 1.).abs() < 1e-2, "{}", largest);
+    }
+
+    #[test]
+    fn zero_one_test_separates_chaotic_and_periodic_logistic_maps() {
+        let chaotic = zero_one_test(&logistic_map(4., 1000), 20, 42);
+        let periodic = zero_one_test(&logistic_map(3.2, 1000), 20, 42);
+        assert!(chaotic > 0.9, "{}", chaotic);
+        assert!(periodic < 0.1, "{}", periodic);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+mod basins;
+mod continuation;
+mod equilibria;
+mod global_sensitivity;
+mod limit_cycles;
+mod lyapunov;
+mod parallel;
+mod phase_plane;
+mod poincare;
+mod uncertainty;
+
+pub use basins::{basin_map, BasinMap, Outcome};
+pub use continuation::{
+    continue_equilibria, orbit_diagram, Bifurcation, BifurcationKind, Branch, ContinuationOptions,
+    OrbitDiagram,
+};
+pub use equilibria::{find_equilibria, newton, print_equilibrium_table, Equilibrium, Stability};
+pub use global_sensitivity::{
+    morris_screening, sobol_indices, MorrisEffects, MorrisOptions, ParameterRanges, SobolIndices,
+    SobolOptions,
+};
+pub use limit_cycles::{
+    peaks, period, refine_cycle, troughs, LimitCycle, Oscillation, PeriodMethod,
+};
+pub use lyapunov::{largest_lyapunov_exponent, lyapunov_spectrum, zero_one_test, LyapunovOptions};
+pub use phase_plane::{initial_condition_grid, PhasePortrait, Segment};
+pub use poincare::{poincare_section, PoincareSection, Section};
+pub use uncertainty::{InputDistribution, MonteCarloOptions, UncertaintyPropagation};
\ No newline at end of file
@@ -0,0 +1,25 @@
+use std::thread;
+
+/// `f(0), ..., f(n - 1)` in order, with the indices shared out in contiguous chunks over the
+/// available threads.
+pub(super) fn parallel_map<T, F>(n: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync,
+{
+    let n_threads = thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk = n.div_ceil(n_threads).max(1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..n)
+            .step_by(chunk)
+            .map(|start| {
+                let f = &f;
+                scope.spawn(move || (start..(start + chunk).min(n)).map(f).collect::<Vec<T>>())
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
\ No newline at end of file
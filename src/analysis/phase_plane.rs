@@ -0,0 +1,237 @@
+use super::{find_equilibria, Equilibrium};
+use crate::ode_solvers::{ODESolver2, RightHandSide};
+use ndarray::{array, s, Array1, Array2};
+
+type Field<'a> = Box<dyn Fn(f64, f64, f64) -> (f64, f64) + 'a>;
+
+/// A straight piece of a curve in the phase plane, `(start, end)`.
+pub type Segment = ((f64, f64), (f64, f64));
+
+/// Everything drawn on the phase plane of a planar model `x' = f1(t, x, y)`, `y' = f2(t, x, y)`
+/// over `bounds`. The field and the nullclines are evaluated at `t = 0`.
+pub struct PhasePortrait<'a> {
+    f: Field<'a>,
+    pub bounds: [(f64, f64); 2],
+    pub labels: [&'a str; 2],
+    pub equilibria: Vec<Equilibrium>,
+    pub trajectories: Vec<(Array1<f64>, Array1<f64>)>,
+}
+
+impl<'a> PhasePortrait<'a> {
+    pub fn new<F, G>(f1: F, f2: G, bounds: [(f64, f64); 2], labels: [&'a str; 2]) -> Self
+    where
+        F: Fn(f64, f64, f64) -> f64 + 'a,
+        G: Fn(f64, f64, f64) -> f64 + 'a,
+    {
+        Self::from_field(move |t, x, y| (f1(t, x, y), f2(t, x, y)), bounds, labels)
+    }
+
+    /// `new` with both derivatives from one call `f(t, x, y) = (x', y')`.
+    pub fn from_field<F>(f: F, bounds: [(f64, f64); 2], labels: [&'a str; 2]) -> Self
+    where
+        F: Fn(f64, f64, f64) -> (f64, f64) + 'a,
+    {
+        Self {
+            f: Box::new(f),
+            bounds,
+            labels,
+            equilibria: Vec::new(),
+            trajectories: Vec::new(),
+        }
+    }
+
+    /// The portrait of a model with its equilibria inside `bounds` already found.
+    pub fn from_rhs<R: RightHandSide>(
+        rhs: &'a R,
+        bounds: [(f64, f64); 2],
+        labels: [&'a str; 2],
+    ) -> Self {
+        Self::from_field(
+            |t, x, y| {
+                let f = rhs.f(t, &array![x, y]);
+                (f[0], f[1])
+            },
+            bounds,
+            labels,
+        )
+        .with_equilibria(find_equilibria(rhs, bounds, 20))
+    }
+
+    pub fn with_equilibria(mut self, equilibria: Vec<Equilibrium>) -> Self {
+        self.equilibria = equilibria;
+        self
+    }
+
+    /// Adds the trajectory from each initial condition up to `tn`, cut where it first leaves the
+    /// box so a diverging orbit does not stretch the plot.
+    pub fn with_trajectories(
+        mut self,
+        ode_solver: ODESolver2,
+        initial_conditions: &[(f64, f64)],
+        tn: f64,
+        n_steps: usize,
+    ) -> Self {
+        let t = Array1::linspace(0., tn, n_steps + 1);
+        let h = tn / n_steps as f64;
+        for &(x_0, y_0) in initial_conditions {
+            let (xt, yt) =
+                ode_solver.solve_coupled(|t, x, y| (self.f)(t, x, y), h, n_steps, &t, x_0, y_0);
+            let end = (0..xt.len())
+                .position(|i| !self.contains(xt[i], yt[i]))
+                .unwrap_or(xt.len());
+            if end > 1 {
+                self.trajectories.push((
+                    xt.slice(s![..end]).to_owned(),
+                    yt.slice(s![..end]).to_owned(),
+                ));
+            }
+        }
+        self
+    }
+
+    pub fn field(&self, x: f64, y: f64) -> (f64, f64) {
+        (self.f)(0., x, y)
+    }
+
+    /// Arrows of the direction field at the centres of an `n x n` grid of cells, all scaled to
+    /// 80% of a cell so the field shows direction rather than speed.
+    pub fn vector_field(&self, n: usize) -> Vec<Segment> {
+        let [(x_min, x_max), (y_min, y_max)] = self.bounds;
+        let (dx, dy) = ((x_max - x_min) / n as f64, (y_max - y_min) / n as f64);
+        let mut arrows = Vec::with_capacity(n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let (x, y) = (x_min + (i as f64 + 0.5) * dx, y_min + (j as f64 + 0.5) * dy);
+                let (u, v) = self.field(x, y);
+                // normalise in cell units, since the axes usually have very different scales
+                let (u, v) = (u / dx, v / dy);
+                let norm = u.hypot(v);
+                if norm == 0. || !norm.is_finite() {
+                    continue;
+                }
+                let (u, v) = (0.4 * u / norm * dx, 0.4 * v / norm * dy);
+                arrows.push(((x - u, y - v), (x + u, y + v)));
+            }
+        }
+        arrows
+    }
+
+    /// The `f1 = 0` and `f2 = 0` nullclines by marching squares on a
+    /// `resolution x resolution` grid of cells.
+    pub fn nullclines(&self, resolution: usize) -> [Vec<Segment>; 2] {
+        [
+            contour(|x, y| self.field(x, y).0, self.bounds, resolution),
+            contour(|x, y| self.field(x, y).1, self.bounds, resolution),
+        ]
+    }
+
+    fn contains(&self, x: f64, y: f64) -> bool {
+        let [(x_min, x_max), (y_min, y_max)] = self.bounds;
+        x >= x_min && x <= x_max && y >= y_min && y <= y_max
+    }
+}
+
+/// `n x n` initial conditions evenly spread over the interior of `bounds`.
+pub fn initial_condition_grid(bounds: [(f64, f64); 2], n: usize) -> Vec<(f64, f64)> {
+    let [(x_min, x_max), (y_min, y_max)] = bounds;
+    let (dx, dy) = ((x_max - x_min) / n as f64, (y_max - y_min) / n as f64);
+    (0..n)
+        .flat_map(|i| {
+            (0..n).map(move |j| (x_min + (i as f64 + 0.5) * dx, y_min + (j as f64 + 0.5) * dy))
+        })
+        .collect()
+}
+
+/// The zero level set of `g`. A cell whose corners change sign on all four edges is split into
+/// two segments pairing neighbouring edges, which is enough at plotting resolution.
+fn contour<F>(g: F, bounds: [(f64, f64); 2], resolution: usize) -> Vec<Segment>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    let [(x_min, x_max), (y_min, y_max)] = bounds;
+    let xs = Array1::linspace(x_min, x_max, resolution + 1);
+    let ys = Array1::linspace(y_min, y_max, resolution + 1);
+    let values = Array2::from_shape_fn((xs.len(), ys.len()), |(i, j)| g(xs[i], ys[j]));
+
+    marching_squares(xs.len(), ys.len(), |a, b| {
+        let (value_a, value_b) = (values[a], values[b]);
+        if (value_a > 0.) == (value_b > 0.) || !value_a.is_finite() || !value_b.is_finite() {
+            return None;
+        }
+        let s = value_a / (value_a - value_b);
+        Some((
+            xs[a.0] + s * (xs[b.0] - xs[a.0]),
+            ys[a.1] + s * (ys[b.1] - ys[a.1]),
+        ))
+    })
+}
+
+/// Segments joining the crossings on the edges of each cell of an `nx x ny` grid of nodes, where
+/// `crossing` places the curve on the edge between two nodes, if it crosses it.
+pub(super) fn marching_squares<C>(nx: usize, ny: usize, crossing: C) -> Vec<Segment>
+where
+    C: Fn((usize, usize), (usize, usize)) -> Option<(f64, f64)>,
+{
+    let mut segments = Vec::new();
+    for i in 0..nx - 1 {
+        for j in 0..ny - 1 {
+            let points: Vec<(f64, f64)> = [
+                ((i, j), (i + 1, j)),
+                ((i + 1, j), (i + 1, j + 1)),
+                ((i + 1, j + 1), (i, j + 1)),
+                ((i, j + 1), (i, j)),
+            ]
+            .into_iter()
+            .filter_map(|(a, b)| crossing(a, b))
+            .collect();
+            for pair in points.chunks_exact(2) {
+                segments.push((pair[0], pair[1]));
+            }
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn length(segments: &[Segment]) -> f64 {
+        segments
+            .iter()
+            .map(|((x_a, y_a), (x_b, y_b))| (x_b - x_a).hypot(y_b - y_a))
+            .sum()
+    }
+
+    #[test]
+    fn nullclines_of_a_linear_system_are_its_lines() {
+        // x' = x - y vanishes on y = x and y' = x + y - 1 on y = 1 - x; the box is offset so no
+        // grid node lies on either line
+        let portrait = PhasePortrait::new(
+            |_, x, y| x - y,
+            |_, x, y| x + y - 1.,
+            [(-2., 2.), (-1.7, 2.3)],
+            ["x", "y"],
+        );
+        let [first, second] = portrait.nullclines(9);
+        assert!(first.iter().all(|((x_a, y_a), (x_b, y_b))| {
+            (y_a - x_a).abs() < 1e-12 && (y_b - x_b).abs() < 1e-12
+        }));
+        assert!(second.iter().all(|((x_a, y_a), (x_b, y_b))| {
+            (x_a + y_a - 1.).abs() < 1e-12 && (x_b + y_b - 1.).abs() < 1e-12
+        }));
+        assert!((length(&first) - 3.7 * 2_f64.sqrt()).abs() < 1e-9);
+        assert!((length(&second) - 3.3 * 2_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn arrows_point_along_a_uniform_field() {
+        let portrait =
+            PhasePortrait::new(|_, _, _| 3., |_, _, _| 0., [(0., 4.), (0., 1.)], ["x", "y"]);
+        let arrows = portrait.vector_field(4);
+        assert_eq!(arrows.len(), 16);
+        assert!(arrows
+            .iter()
+            .all(|((x_a, y_a), (x_b, y_b))| (x_b - x_a - 0.8).abs() < 1e-12 && y_a == y_b));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,147 @@
+use crate::ode_solvers::{Event, EventDirection, ODESolver, RootFinder};
+use ndarray::{Array1, Array2};
+use std::f64::consts::PI;
+
+#[derive(Clone)]
+pub enum Section {
+    /// The hyperplane `normal . y = offset`, counted when crossed in `direction`.
+    Hyperplane {
+        normal: Array1<f64>,
+        offset: f64,
+        direction: EventDirection,
+    },
+    /// The times `phase + k period`, a stroboscopic map of a periodically forced model.
+    Stroboscopic { period: f64, phase: f64 },
+}
+
+impl Section {
+    fn event(&self) -> Event<'_> {
+        match self {
+            Section::Hyperplane {
+                normal,
+                offset,
+                direction,
+            } => Event::new(move |_, y| normal.dot(y) - offset, *direction, false),
+            // vanishes exactly once per period, changing sign each time
+            Section::Stroboscopic { period, phase } => Event::new(
+                move |t, _| (PI * (t - phase) / period).sin(),
+                EventDirection::Either,
+                false,
+            ),
+        }
+    }
+}
+
+/// Successive intersections of a trajectory with a section, one row of `y` per time in `t`.
+pub struct PoincareSection {
+    pub t: Array1<f64>,
+    pub y: Array2<f64>,
+}
+
+impl PoincareSection {
+    /// Pairs `(y_k, y_k+1)` of one component, the points of its first-return map.
+    pub fn return_map(&self, component: usize) -> (Array1<f64>, Array1<f64>) {
+        let x = self.y.column(component);
+        let n = x.len().saturating_sub(1);
+        (
+            x.iter().take(n).cloned().collect(),
+            x.iter().skip(1).cloned().collect(),
+        )
+    }
+}
+
+/// Integrates `y' = f(t, y)` with `n_steps` fixed steps from `(t_0, y_0)` and records where the
+/// trajectory meets `section`, located by event detection inside each step. Intersections before
+/// `t_0 + transient` are dropped.
+#[allow(clippy::too_many_arguments)]
+pub fn poincare_section<F>(
+    ode_solver: ODESolver,
+    f: F,
+    section: &Section,
+    h: f64,
+    n_steps: usize,
+    t_0: f64,
+    y_0: &Array1<f64>,
+    transient: f64,
+) -> PoincareSection
+where
+    F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+{
+    let t = Array1::linspace(t_0, t_0 + n_steps as f64 * h, n_steps + 1);
+    let (_, _, records) = ode_solver.solve_with_events(
+        f,
+        h,
+        n_steps,
+        &t,
+        y_0,
+        &[section.event()],
+        RootFinder::Illinois,
+    );
+    let kept: Vec<_> = records
+        .into_iter()
+        .filter(|r| r.t >= t_0 + transient)
+        .collect();
+    PoincareSection {
+        t: kept.iter().map(|r| r.t).collect(),
+        y: Array2::from_shape_fn((kept.len(), y_0.len()), |(i, j)| kept[i].y[j]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+
+    #[test]
+    fn stroboscopic_map_of_a_forced_linear_decay() {
+        // y' = -y + cos t is y = (cos t + sin t) / 2 + (y_0 - 1 / 2) e^(-t), so sampled every
+        // 2 pi it contracts onto 1 / 2 by e^(-2 pi) per period
+        let (y_0, h, n_steps) = (3., 0.01, 2000);
+        let section = Section::Stroboscopic {
+            period: 2. * PI,
+            phase: 0.,
+        };
+        let strobe = poincare_section(
+            ODESolver::new(SolverMethod::Classic4),
+            |t, y| -y + t.cos(),
+            &section,
+            h,
+            n_steps,
+            0.,
+            &array![y_0],
+            0.,
+        );
+        assert_eq!(strobe.t.len(), 3);
+        for (k, (t, y)) in strobe.t.iter().zip(strobe.y.column(0)).enumerate() {
+            let t_k = 2. * PI * (k + 1) as f64;
+            assert!((t - t_k).abs() < 1e-12);
+            assert!((y - 0.5 - (y_0 - 0.5) * (-t_k).exp()).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn hyperplane_crossings_of_the_harmonic_oscillator() {
+        // x = cos t, y = -sin t falls through x = 0 at t = pi / 2 + 2 pi k with y = -1
+        let section = Section::Hyperplane {
+            normal: array![1., 0.],
+            offset: 0.,
+            direction: EventDirection::Falling,
+        };
+        let crossings = poincare_section(
+            ODESolver::new(SolverMethod::Classic4),
+            |_, y| array![y[1], -y[0]],
+            &section,
+            0.01,
+            2000,
+            0.,
+            &array![1., 0.],
+            0.,
+        );
+        assert_eq!(crossings.t.len(), 3);
+        for (k, (t, y)) in crossings.t.iter().zip(crossings.y.rows()).enumerate() {
+            assert!((t - PI / 2. - 2. * PI * k as f64).abs() < 1e-8);
+            assert!(y[0].abs() < 1e-10 && (y[1] + 1.).abs() < 1e-8);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,254 @@
+use super::parallel::parallel_map;
+use crate::ode_solvers::{ODESolver, RightHandSide};
+use crate::sde_solvers::EnsembleStatistics;
+use ndarray::{Array1, Array2};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+const MAX_REJECTIONS: usize = 10_000;
+
+/// Where an uncertain parameter or initial condition is drawn from.
+#[derive(Clone, Debug)]
+pub enum InputDistribution {
+    Uniform {
+        lower: f64,
+        upper: f64,
+    },
+    Normal {
+        mean: f64,
+        sd: f64,
+    },
+    /// `ln x` is normal with mean `mu` and standard deviation `sigma`.
+    LogNormal {
+        mu: f64,
+        sigma: f64,
+    },
+    /// Another distribution conditioned to lie within `[lower, upper]`.
+    Truncated {
+        distribution: Box<InputDistribution>,
+        lower: f64,
+        upper: f64,
+    },
+}
+
+impl InputDistribution {
+    pub fn truncated(self, lower: f64, upper: f64) -> Self {
+        assert!(lower < upper, "empty truncation interval");
+        InputDistribution::Truncated {
+            distribution: Box::new(self),
+            lower,
+            upper,
+        }
+    }
+
+    /// A random draw. Truncated distributions are sampled by rejection.
+    pub fn sample<G: Rng>(&self, rng: &mut G) -> f64 {
+        match self {
+            InputDistribution::Uniform { lower, upper } => {
+                lower + (upper - lower) * rng.gen::<f64>()
+            }
+            InputDistribution::Normal { mean, sd } => {
+                mean + sd * rng.sample::<f64, _>(StandardNormal)
+            }
+            InputDistribution::LogNormal { mu, sigma } => {
+                (mu + sigma * rng.sample::<f64, _>(StandardNormal)).exp()
+            }
+            InputDistribution::Truncated {
+                distribution,
+                lower,
+                upper,
+            } => (0..MAX_REJECTIONS)
+                .map(|_| distribution.sample(rng))
+                .find(|x| x >= lower && x <= upper)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "{:?} has almost no probability in [{}, {}]",
+                        distribution, lower, upper
+                    )
+                }),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Input {
+    /// Index into the model's parameters.
+    Parameter(usize),
+    /// Index into the initial condition.
+    InitialCondition(usize),
+}
+
+#[derive(Clone)]
+pub struct MonteCarloOptions {
+    pub n_runs: usize,
+    /// Quantiles of the ensemble to keep at every time.
+    pub levels: Vec<f64>,
+    pub seed: u64,
+}
+
+impl Default for MonteCarloOptions {
+    fn default() -> Self {
+        Self {
+            n_runs: 1000,
+            levels: vec![0.025, 0.25, 0.5, 0.75, 0.975],
+            seed: 1,
+        }
+    }
+}
+
+/// Monte Carlo propagation of uncertain parameters and initial conditions through a model: every
+/// run draws each uncertain input independently and integrates from `y_0` with the rest held at
+/// the model's values.
+pub struct UncertaintyPropagation<'a, R: RightHandSide> {
+    ode_solver: ODESolver<'a>,
+    rhs: &'a R,
+    y_0: Array1<f64>,
+    inputs: Vec<(&'static str, Input, InputDistribution)>,
+}
+
+impl<'a, R: RightHandSide + Sync> UncertaintyPropagation<'a, R> {
+    pub fn new(ode_solver: ODESolver<'a>, rhs: &'a R, y_0: &Array1<f64>) -> Self {
+        Self {
+            ode_solver,
+            rhs,
+            y_0: y_0.clone(),
+            inputs: Vec::new(),
+        }
+    }
+
+    /// Draws the model parameter `name` from `distribution`.
+    pub fn uncertain(mut self, name: &'static str, distribution: InputDistribution) -> Self {
+        let k = self
+            .rhs
+            .parameters()
+            .iter()
+            .position(|(n, _)| *n == name)
+            .unwrap_or_else(|| panic!("the model has no parameter {}", name));
+        self.inputs.push((name, Input::Parameter(k), distribution));
+        self
+    }
+
+    /// Draws the initial value of `component`, called `name`, from `distribution`.
+    pub fn uncertain_initial_condition(
+        mut self,
+        name: &'static str,
+        component: usize,
+        distribution: InputDistribution,
+    ) -> Self {
+        self.inputs
+            .push((name, Input::InitialCondition(component), distribution));
+        self
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.inputs.iter().map(|(name, _, _)| *name).collect()
+    }
+
+    /// The inputs of each run, one row per run in the order the inputs were added. The same
+    /// `n_runs` and `seed` give the draws `propagate` uses.
+    pub fn samples(&self, n_runs: usize, seed: u64) -> Array2<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut samples = Array2::zeros((n_runs, self.inputs.len()));
+        for mut row in samples.rows_mut() {
+            for (x, (_, _, distribution)) in row.iter_mut().zip(&self.inputs) {
+                *x = distribution.sample(&mut rng);
+            }
+        }
+        samples
+    }
+
+    /// Runs the ensemble with `n_steps` steps of size `h` over the grid `t`, sharing the runs out
+    /// over the available threads, and returns its mean and quantiles at every time.
+    pub fn propagate(
+        &self,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        options: &MonteCarloOptions,
+    ) -> EnsembleStatistics {
+        let samples = self.samples(options.n_runs, options.seed);
+        let run = |i: usize| {
+            let (mut p, mut y_0) = (self.rhs.parameter_values(), self.y_0.clone());
+            for ((_, input, _), value) in self.inputs.iter().zip(samples.row(i)) {
+                match *input {
+                    Input::Parameter(k) => p[k] = *value,
+                    Input::InitialCondition(j) => y_0[j] = *value,
+                }
+            }
+            self.ode_solver
+                .solve(|t, y| self.rhs.eval(t, y, &p), h, n_steps, t, &y_0)
+        };
+
+        let paths = parallel_map(options.n_runs, run);
+        EnsembleStatistics::from_paths(&paths, &options.levels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dual::Real;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+
+    struct Decay {
+        k: f64,
+    }
+
+    impl RightHandSide for Decay {
+        fn parameters(&self) -> Vec<(&'static str, f64)> {
+            vec![("k", self.k)]
+        }
+
+        fn eval<T: Real>(&self, _: f64, y: &Array1<T>, p: &Array1<T>) -> Array1<T> {
+            array![-p[0] * y[0]]
+        }
+    }
+
+    #[test]
+    fn envelope_of_a_uniform_decay_rate() {
+        // y = e^(-k t) falls with k, so its quantile at level q is e^(-k_(1 - q) t) and a 90%
+        // envelope should hold 90% of fresh runs
+        let (h, n_steps, t_end) = (0.02, 100, 2.);
+        let t = Array1::linspace(0., t_end, n_steps + 1);
+        let decay = Decay { k: 1. };
+        let propagation = UncertaintyPropagation::new(
+            ODESolver::new(SolverMethod::Classic4),
+            &decay,
+            &array![1.],
+        )
+        .uncertain(
+            "k",
+            InputDistribution::Uniform {
+                lower: 0.5,
+                upper: 1.5,
+            },
+        );
+        let options = MonteCarloOptions {
+            n_runs: 2000,
+            levels: vec![0.05, 0.95],
+            seed: 3,
+        };
+        let stats = propagation.propagate(h, n_steps, &t, &options);
+
+        let (lower, upper) = (
+            stats.quantile(0.05)[[n_steps, 0]],
+            stats.quantile(0.95)[[n_steps, 0]],
+        );
+        assert!((lower - (-1.45 * t_end).exp()).abs() < 1e-2, "{}", lower);
+        assert!((upper - (-0.55 * t_end).exp()).abs() < 1e-2, "{}", upper);
+        let mean = ((-0.5 * t_end).exp() - (-1.5 * t_end).exp()) / t_end;
+        assert!((stats.mean[[n_steps, 0]] - mean).abs() < 1e-2);
+
+        let fresh = propagation.samples(2000, 4);
+        let covered = fresh
+            .column(0)
+            .iter()
+            .map(|k| (-k * t_end).exp())
+            .filter(|y| *y >= lower && *y <= upper)
+            .count();
+        let coverage = covered as f64 / 2000.;
+        assert!((coverage - 0.9).abs() < 0.03, "{}", coverage);
+    }
+}
\ No newline at end of file
@@ -1,14 +1,21 @@
+use crate::analysis::{BasinMap, Branch, Outcome, PhasePortrait, Segment, Stability};
+use crate::fitting::Chain;
+use crate::sde_solvers::EnsembleStatistics;
 use ndarray::Array1;
-use plotters::backend::BitMapBackend;
-use plotters::chart::{ChartBuilder, LabelAreaPosition};
-use plotters::drawing::IntoDrawingArea;
-use plotters::element::PathElement;
+use plotters::backend::{BitMapBackend, DrawingBackend, SVGBackend};
+use plotters::chart::{ChartBuilder, LabelAreaPosition, SeriesLabelPosition};
+use plotters::coord::Shift;
+use plotters::drawing::{DrawingArea, IntoDrawingArea};
+use plotters::element::{Circle, EmptyElement, PathElement, Polygon, Rectangle, Text};
 use plotters::prelude::IntoFont;
 use plotters::series::LineSeries;
 use plotters::style::{Color, RGBColor, BLACK, BLUE, CYAN, GREEN, MAGENTA, RED, WHITE, YELLOW};
 
 const COLORS: [&RGBColor; 6] = [&RED, &GREEN, &BLUE, &YELLOW, &CYAN, &MAGENTA];
 
+/// `(x, lower, upper, label)` of a shaded region.
+pub type Band<'a> = (Array1<f64>, Array1<f64>, Array1<f64>, &'a str);
+
 pub fn draw_bitmap_line_chart(
     file_name: &str,
     caption: &str,
@@ -18,11 +25,11 @@ pub fn draw_bitmap_line_chart(
         lines
             .iter()
             .map(|(x, _, _)| x.iter().rfold(0., |x, y| f64::min(x, *y)))
-            .rfold(0., |x, y| f64::min(x, y)),
+            .rfold(0., f64::min),
         lines
             .iter()
             .map(|(x, _, _)| x.iter().rfold(0., |x, y| f64::max(x, *y)))
-            .rfold(0., |x, y| f64::max(x, y)),
+            .rfold(0., f64::max),
     );
 
     println!("x max = {} min = {}", x_max, x_min);
@@ -31,11 +38,11 @@ pub fn draw_bitmap_line_chart(
         lines
             .iter()
             .map(|(_, y, _)| y.iter().rfold(0., |x, y| f64::min(x, *y)))
-            .rfold(0., |x, y| f64::min(x, y)),
+            .rfold(0., f64::min),
         lines
             .iter()
             .map(|(_, y, _)| y.iter().rfold(0., |x, y| f64::max(x, *y)))
-            .rfold(0., |x, y| f64::max(x, y)),
+            .rfold(0., f64::max),
     );
 
     println!("y max = {} min = {}", y_max, y_min);
@@ -59,15 +66,742 @@ pub fn draw_bitmap_line_chart(
             .draw_series(LineSeries::new(x.into_iter().zip(y), color))
             .unwrap()
             .label(label)
-            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.clone()));
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], *color));
     }
 
     chart
         .configure_series_labels()
-        .background_style(&WHITE.mix(0.8))
-        .border_style(&BLACK)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
         .draw()
         .unwrap();
 
     root_drawing_area.present().unwrap();
+}
+
+/// Draws each band as a shaded region between its lower and upper curves, with the lines on top.
+/// Bands and lines are coloured in the same order, so a band and the line it surrounds should
+/// share an index.
+pub fn draw_bitmap_band_chart(
+    file_name: &str,
+    caption: &str,
+    bands: Vec<Band>,
+    lines: Vec<(Array1<f64>, Array1<f64>, &str)>,
+) {
+    let xs = bands
+        .iter()
+        .map(|(x, _, _, _)| x)
+        .chain(lines.iter().map(|(x, _, _)| x));
+    let ys = bands
+        .iter()
+        .flat_map(|(_, lower, upper, _)| [lower, upper])
+        .chain(lines.iter().map(|(_, y, _)| y));
+    let (x_min, x_max) = range(xs);
+    let (y_min, y_max) = range(ys);
+
+    let root_drawing_area = BitMapBackend::new(file_name, (1024, 768)).into_drawing_area();
+    root_drawing_area.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root_drawing_area)
+        .caption(caption, ("sans-serif", 40).into_font())
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)
+        .unwrap();
+
+    chart.configure_mesh().draw().unwrap();
+
+    for (i, (x, lower, upper, label)) in bands.into_iter().enumerate() {
+        let color = COLORS[i % 6];
+        let outline: Vec<(f64, f64)> = x
+            .iter()
+            .cloned()
+            .zip(upper.iter().cloned())
+            .chain(x.iter().cloned().zip(lower.iter().cloned()).rev())
+            .collect();
+        chart
+            .draw_series(std::iter::once(Polygon::new(outline, color.mix(0.2))))
+            .unwrap()
+            .label(label)
+            .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], color.mix(0.2)));
+    }
+
+    for (i, (x, y, label)) in lines.into_iter().enumerate() {
+        let color = COLORS[i % 6];
+        chart
+            .draw_series(LineSeries::new(x.into_iter().zip(y), color))
+            .unwrap()
+            .label(label)
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], *color));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root_drawing_area.present().unwrap();
+}
+
+/// The median of each component of an ensemble over `t`, one per label, inside its 50% band and a
+/// lighter 95% band. The statistics must hold the 2.5, 25, 50, 75 and 97.5% quantiles.
+pub fn draw_bitmap_envelope_chart(
+    file_name: &str,
+    caption: &str,
+    t: &Array1<f64>,
+    statistics: &EnsembleStatistics,
+    labels: &[&str],
+) {
+    let quantile = |level: f64, j: usize| statistics.quantile(level).column(j);
+    let (x_min, x_max) = range(std::iter::once(t));
+    let y_min = statistics
+        .quantile(0.025)
+        .fold(0., |min, y| f64::min(min, *y));
+    let y_max = statistics
+        .quantile(0.975)
+        .fold(0., |max, y| f64::max(max, *y));
+
+    let root_drawing_area = BitMapBackend::new(file_name, (1024, 768)).into_drawing_area();
+    root_drawing_area.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root_drawing_area)
+        .caption(caption, ("sans-serif", 40).into_font())
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)
+        .unwrap();
+
+    chart.configure_mesh().draw().unwrap();
+
+    for (j, label) in labels.iter().enumerate() {
+        let color = COLORS[j % 6];
+        for (lower, upper, opacity, band) in
+            [(0.025, 0.975, 0.15, "95%"), (0.25, 0.75, 0.35, "50%")]
+        {
+            let outline: Vec<(f64, f64)> = t
+                .iter()
+                .cloned()
+                .zip(quantile(upper, j).iter().cloned())
+                .chain(
+                    t.iter()
+                        .cloned()
+                        .zip(quantile(lower, j).iter().cloned())
+                        .rev(),
+                )
+                .collect();
+            chart
+                .draw_series(std::iter::once(Polygon::new(outline, color.mix(opacity))))
+                .unwrap()
+                .label(format!("{} {} band", label, band))
+                .legend(move |(x, y)| {
+                    Rectangle::new([(x, y - 5), (x + 20, y + 5)], color.mix(opacity))
+                });
+        }
+        chart
+            .draw_series(LineSeries::new(
+                t.iter().cloned().zip(quantile(0.5, j).iter().cloned()),
+                color.stroke_width(2),
+            ))
+            .unwrap()
+            .label(format!("{} median", label))
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2)));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root_drawing_area.present().unwrap();
+}
+
+/// Draws each series as unconnected dots, for samples such as orbit diagrams or Poincaré sections.
+/// The axes fit the points rather than starting at zero.
+pub fn draw_bitmap_scatter_chart(
+    file_name: &str,
+    caption: &str,
+    points: Vec<(Array1<f64>, Array1<f64>, &str)>,
+) {
+    let (x_min, x_max) = padded_range(points.iter().map(|(x, _, _)| x));
+    let (y_min, y_max) = padded_range(points.iter().map(|(_, y, _)| y));
+
+    let root_drawing_area = BitMapBackend::new(file_name, (1024, 768)).into_drawing_area();
+    root_drawing_area.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root_drawing_area)
+        .caption(caption, ("sans-serif", 40).into_font())
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)
+        .unwrap();
+
+    chart.configure_mesh().draw().unwrap();
+
+    for (i, (x, y, label)) in points.into_iter().enumerate() {
+        let color = COLORS[i % 6];
+        chart
+            .draw_series(
+                x.into_iter()
+                    .zip(y)
+                    .map(|p| Circle::new(p, 2, color.filled())),
+            )
+            .unwrap()
+            .label(label)
+            .legend(|(x, y)| Circle::new((x + 10, y), 3, color.filled()));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root_drawing_area.present().unwrap();
+}
+
+/// Grouped bars: one group per label holding the value of every series for it, such as the
+/// sensitivity indices of each parameter.
+pub fn draw_bitmap_bar_chart(
+    file_name: &str,
+    caption: &str,
+    labels: &[&str],
+    series: Vec<(Array1<f64>, &str)>,
+) {
+    // bars start at zero, so the axis always includes it
+    let (y_min, y_max) = range(series.iter().map(|(y, _)| y));
+    let margin = 0.1 * (y_max - y_min).max(f64::MIN_POSITIVE);
+    let y_min = if y_min < 0. { y_min - margin } else { 0. };
+    let n_groups = labels.len();
+
+    let root_drawing_area = BitMapBackend::new(file_name, (1024, 768)).into_drawing_area();
+    root_drawing_area.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root_drawing_area)
+        .caption(caption, ("sans-serif", 40).into_font())
+        .set_label_area_size(LabelAreaPosition::Left, 60)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(-0.5..n_groups as f64 - 0.5, y_min..y_max + margin)
+        .unwrap();
+
+    // groups sit at whole numbers, which are always among twice as many ticks as groups
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_labels(2 * n_groups)
+        .x_label_formatter(&|x| {
+            let i = x.round();
+            if (x - i).abs() < 1e-9 && i >= 0. && (i as usize) < n_groups {
+                labels[i as usize].to_string()
+            } else {
+                String::new()
+            }
+        })
+        .draw()
+        .unwrap();
+
+    let width = 0.8 / series.len() as f64;
+    for (k, (y, label)) in series.into_iter().enumerate() {
+        let color = COLORS[k % 6];
+        let offset = -0.4 + k as f64 * width;
+        chart
+            .draw_series(y.iter().enumerate().map(|(i, y_i)| {
+                let x = i as f64 + offset;
+                Rectangle::new([(x, 0.), (x + width, *y_i)], color.filled())
+            }))
+            .unwrap()
+            .label(label)
+            .legend(|(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], color.filled()));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root_drawing_area.present().unwrap();
+}
+
+/// Branches of equilibria against their parameter, first state variable only, with stable parts
+/// in thick blue and unstable parts in thin red. Detected bifurcations are marked and named, and
+/// `points` (an orbit diagram, say) are scattered on top.
+pub fn draw_bitmap_bifurcation_diagram(
+    file_name: &str,
+    caption: &str,
+    branches: &[Branch],
+    points: Vec<(Array1<f64>, Array1<f64>, &str)>,
+) {
+    let ys: Vec<Array1<f64>> = branches.iter().map(|b| b.y.column(0).to_owned()).collect();
+    let (x_min, x_max) = range(
+        branches
+            .iter()
+            .map(|b| &b.parameter)
+            .chain(points.iter().map(|(x, _, _)| x)),
+    );
+    let (y_min, y_max) = range(ys.iter().chain(points.iter().map(|(_, y, _)| y)));
+
+    let root_drawing_area = BitMapBackend::new(file_name, (1024, 768)).into_drawing_area();
+    root_drawing_area.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root_drawing_area)
+        .caption(caption, ("sans-serif", 40).into_font())
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)
+        .unwrap();
+
+    chart.configure_mesh().draw().unwrap();
+
+    let (mut stable_labelled, mut unstable_labelled) = (false, false);
+    for branch in branches {
+        for (p, y, stable) in branch.segments(0) {
+            let (style, label, labelled) = if stable {
+                (BLUE.stroke_width(3), "stable", &mut stable_labelled)
+            } else {
+                (RED.stroke_width(1), "unstable", &mut unstable_labelled)
+            };
+            let series = chart
+                .draw_series(LineSeries::new(p.into_iter().zip(y), style))
+                .unwrap();
+            if !*labelled {
+                *labelled = true;
+                series
+                    .label(label)
+                    .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], style));
+            }
+        }
+        chart
+            .draw_series(branch.bifurcations.iter().map(|b| {
+                EmptyElement::at((b.parameter, b.y[0]))
+                    + Circle::new((0, 0), 5, BLACK.filled())
+                    + Text::new(
+                        format!("{} ({:.3})", b.kind, b.parameter),
+                        (8, -16),
+                        ("sans-serif", 16).into_font(),
+                    )
+            }))
+            .unwrap();
+    }
+
+    for (i, (x, y, label)) in points.into_iter().enumerate() {
+        // skip the branch colours
+        let color = [&GREEN, &MAGENTA, &CYAN][i % 3];
+        chart
+            .draw_series(
+                x.into_iter()
+                    .zip(y)
+                    .map(|p| Circle::new(p, 2, color.filled())),
+            )
+            .unwrap()
+            .label(label)
+            .legend(|(x, y)| Circle::new((x + 10, y), 3, color.filled()));
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root_drawing_area.present().unwrap();
+}
+
+pub fn draw_bitmap_phase_portrait(file_name: &str, caption: &str, portrait: &PhasePortrait) {
+    draw_phase_portrait(
+        BitMapBackend::new(file_name, (1024, 768)).into_drawing_area(),
+        caption,
+        portrait,
+    );
+}
+
+pub fn draw_svg_phase_portrait(file_name: &str, caption: &str, portrait: &PhasePortrait) {
+    draw_phase_portrait(
+        SVGBackend::new(file_name, (1024, 768)).into_drawing_area(),
+        caption,
+        portrait,
+    );
+}
+
+/// Direction field in grey, the nullclines of the first and second variable in red and blue,
+/// trajectories in black and equilibria as filled (stable), open (unstable) or red (centre or
+/// non-hyperbolic) circles.
+fn draw_phase_portrait<DB: DrawingBackend>(
+    root_drawing_area: DrawingArea<DB, Shift>,
+    caption: &str,
+    portrait: &PhasePortrait,
+) {
+    let [(x_min, x_max), (y_min, y_max)] = portrait.bounds;
+    root_drawing_area.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root_drawing_area)
+        .caption(caption, ("sans-serif", 40).into_font())
+        .set_label_area_size(LabelAreaPosition::Left, 60)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)
+        .unwrap();
+
+    chart
+        .configure_mesh()
+        .x_desc(portrait.labels[0])
+        .y_desc(portrait.labels[1])
+        .draw()
+        .unwrap();
+
+    let grey = RGBColor(160, 160, 160);
+    let arrows = portrait.vector_field(20);
+    chart
+        .draw_series(
+            arrows
+                .iter()
+                .flat_map(|&arrow| arrow_paths(arrow, portrait.bounds))
+                .map(|path| PathElement::new(path, grey)),
+        )
+        .unwrap();
+
+    let [nullcline1, nullcline2] = portrait.nullclines(200);
+    for (segments, color, label) in [
+        (nullcline1, &RED, format!("{}' = 0", portrait.labels[0])),
+        (nullcline2, &BLUE, format!("{}' = 0", portrait.labels[1])),
+    ] {
+        chart
+            .draw_series(
+                segments
+                    .iter()
+                    .map(|&(a, b)| PathElement::new(vec![a, b], color.stroke_width(2))),
+            )
+            .unwrap()
+            .label(label)
+            .legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 20, y)], color.stroke_width(2))
+            });
+    }
+
+    for (i, (x, y)) in portrait.trajectories.iter().enumerate() {
+        let series = chart
+            .draw_series(LineSeries::new(
+                thin_path(x, y, portrait.bounds),
+                BLACK.stroke_width(2),
+            ))
+            .unwrap();
+        if i == 0 {
+            series
+                .label("trajectories")
+                .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK));
+        }
+    }
+
+    let groups = [
+        ("stable", BLACK.filled()),
+        ("unstable", BLACK.stroke_width(2)),
+        ("centre, non-hyperbolic or singular", RED.stroke_width(2)),
+    ];
+    for (label, style) in groups {
+        let points: Vec<(f64, f64)> = portrait
+            .equilibria
+            .iter()
+            .filter(|e| match e.stability {
+                Stability::Centre | Stability::NonHyperbolic | Stability::Singular => {
+                    label == groups[2].0
+                }
+                stability if stability.is_stable() => label == groups[0].0,
+                _ => label == groups[1].0,
+            })
+            .map(|e| (e.y[0], e.y[1]))
+            .collect();
+        if points.is_empty() {
+            continue;
+        }
+        chart
+            .draw_series(points.into_iter().map(|p| Circle::new(p, 6, style)))
+            .unwrap()
+            .label(label)
+            .legend(move |(x, y)| Circle::new((x + 10, y), 5, style));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root_drawing_area.present().unwrap();
+}
+
+/// Each grid node as a cell coloured by its outcome, with the separatrix between basins in black.
+pub fn draw_bitmap_basin_map(file_name: &str, caption: &str, basins: &BasinMap) {
+    let [(x_min, x_max), (y_min, y_max)] = basins.bounds;
+    let root_drawing_area = BitMapBackend::new(file_name, (1024, 768)).into_drawing_area();
+    root_drawing_area.fill(&WHITE).unwrap();
+
+    let mut chart = ChartBuilder::on(&root_drawing_area)
+        .caption(caption, ("sans-serif", 40).into_font())
+        .set_label_area_size(LabelAreaPosition::Left, 60)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(x_min..x_max, y_min..y_max)
+        .unwrap();
+
+    let color = |outcome: Outcome| match outcome {
+        Outcome::Extinction => RGBColor(190, 190, 190),
+        Outcome::FirstSurvives => RGBColor(240, 150, 150),
+        Outcome::SecondSurvives => RGBColor(150, 170, 240),
+        Outcome::Coexistence => RGBColor(150, 220, 150),
+        Outcome::Unsettled => RGBColor(240, 220, 120),
+    };
+    // cells reach halfway to the neighbouring nodes
+    let edges = |v: &Array1<f64>| -> Vec<f64> {
+        let n = v.len();
+        (0..=n)
+            .map(|i| match i {
+                0 => v[0],
+                i if i == n => v[n - 1],
+                i => 0.5 * (v[i - 1] + v[i]),
+            })
+            .collect()
+    };
+    let (x_edges, y_edges) = (edges(&basins.x), edges(&basins.y));
+    for outcome in basins.present() {
+        let style = color(outcome).filled();
+        chart
+            .draw_series(
+                basins
+                    .outcomes
+                    .indexed_iter()
+                    .filter(|(_, o)| **o == outcome)
+                    .map(|((i, j), _)| {
+                        Rectangle::new(
+                            [(x_edges[i], y_edges[j]), (x_edges[i + 1], y_edges[j + 1])],
+                            style,
+                        )
+                    }),
+            )
+            .unwrap()
+            .label(outcome.to_string())
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 20, y + 5)], style));
+    }
+
+    chart
+        .configure_mesh()
+        .disable_mesh()
+        .x_desc(basins.labels[0])
+        .y_desc(basins.labels[1])
+        .draw()
+        .unwrap();
+
+    if !basins.separatrix.is_empty() {
+        chart
+            .draw_series(
+                basins
+                    .separatrix
+                    .iter()
+                    .map(|&(a, b)| PathElement::new(vec![a, b], BLACK.stroke_width(2))),
+            )
+            .unwrap()
+            .label("separatrix")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK.stroke_width(2)));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()
+        .unwrap();
+
+    root_drawing_area.present().unwrap();
+}
+
+/// One panel per parameter with its draws in chain order, to check mixing.
+pub fn draw_bitmap_trace_plot(file_name: &str, caption: &str, chain: &Chain) {
+    let root_drawing_area = BitMapBackend::new(file_name, (1024, 768)).into_drawing_area();
+    root_drawing_area.fill(&WHITE).unwrap();
+    let root_drawing_area = root_drawing_area
+        .titled(caption, ("sans-serif", 40).into_font())
+        .unwrap();
+    let panels = root_drawing_area.split_evenly((chain.names.len(), 1));
+
+    let n = chain.samples.nrows();
+    for (k, panel) in panels.iter().enumerate() {
+        let x = chain.samples.column(k).to_owned();
+        let (y_min, y_max) = padded_range(std::iter::once(&x));
+        let mut chart = ChartBuilder::on(panel)
+            .margin(5)
+            .set_label_area_size(LabelAreaPosition::Left, 80)
+            .set_label_area_size(LabelAreaPosition::Bottom, 30)
+            .build_cartesian_2d(0.0..n as f64, y_min..y_max)
+            .unwrap();
+        chart
+            .configure_mesh()
+            .disable_mesh()
+            .y_desc(chain.names[k])
+            .draw()
+            .unwrap();
+        chart
+            .draw_series(LineSeries::new(
+                x.iter().enumerate().map(|(i, x_i)| (i as f64, *x_i)),
+                COLORS[k % 6],
+            ))
+            .unwrap();
+    }
+
+    root_drawing_area.present().unwrap();
+}
+
+/// Pairwise scatter of the draws below the diagonal and a histogram of each parameter on it.
+pub fn draw_bitmap_corner_plot(file_name: &str, caption: &str, chain: &Chain) {
+    let d = chain.names.len();
+    let root_drawing_area = BitMapBackend::new(file_name, (1024, 1024)).into_drawing_area();
+    root_drawing_area.fill(&WHITE).unwrap();
+    let root_drawing_area = root_drawing_area
+        .titled(caption, ("sans-serif", 40).into_font())
+        .unwrap();
+    let panels = root_drawing_area.split_evenly((d, d));
+
+    let columns: Vec<Array1<f64>> = (0..d).map(|k| chain.samples.column(k).to_owned()).collect();
+    let ranges: Vec<(f64, f64)> = columns
+        .iter()
+        .map(|x| padded_range(std::iter::once(x)))
+        .collect();
+    // at most about 2000 dots per scatter
+    let every = (chain.samples.nrows() / 2000).max(1);
+    let n_bins = 30;
+
+    for i in 0..d {
+        for j in 0..=i {
+            let panel = &panels[i * d + j];
+            let (x_min, x_max) = ranges[j];
+            let x_desc = if i == d - 1 { chain.names[j] } else { "" };
+            if i == j {
+                let width = (x_max - x_min) / n_bins as f64;
+                let mut counts = vec![0usize; n_bins];
+                for x in &columns[j] {
+                    let bin = (((x - x_min) / width) as usize).min(n_bins - 1);
+                    counts[bin] += 1;
+                }
+                let highest = *counts.iter().max().unwrap() as f64;
+                let mut chart = ChartBuilder::on(panel)
+                    .margin(5)
+                    .set_label_area_size(LabelAreaPosition::Left, 50)
+                    .set_label_area_size(LabelAreaPosition::Bottom, 40)
+                    .build_cartesian_2d(x_min..x_max, 0.0..1.05 * highest)
+                    .unwrap();
+                chart
+                    .configure_mesh()
+                    .disable_mesh()
+                    .x_labels(3)
+                    .y_labels(3)
+                    .x_desc(x_desc)
+                    .draw()
+                    .unwrap();
+                chart
+                    .draw_series(counts.iter().enumerate().map(|(b, count)| {
+                        let left = x_min + b as f64 * width;
+                        Rectangle::new(
+                            [(left, 0.), (left + width, *count as f64)],
+                            BLUE.mix(0.5).filled(),
+                        )
+                    }))
+                    .unwrap();
+            } else {
+                let (y_min, y_max) = ranges[i];
+                let mut chart = ChartBuilder::on(panel)
+                    .margin(5)
+                    .set_label_area_size(LabelAreaPosition::Left, 50)
+                    .set_label_area_size(LabelAreaPosition::Bottom, 40)
+                    .build_cartesian_2d(x_min..x_max, y_min..y_max)
+                    .unwrap();
+                chart
+                    .configure_mesh()
+                    .disable_mesh()
+                    .x_labels(3)
+                    .y_labels(3)
+                    .x_desc(x_desc)
+                    .y_desc(if j == 0 { chain.names[i] } else { "" })
+                    .draw()
+                    .unwrap();
+                chart
+                    .draw_series(
+                        columns[j]
+                            .iter()
+                            .zip(&columns[i])
+                            .step_by(every)
+                            .map(|(x, y)| Circle::new((*x, *y), 1, BLUE.mix(0.4).filled())),
+                    )
+                    .unwrap();
+            }
+        }
+    }
+
+    root_drawing_area.present().unwrap();
+}
+
+/// Drops points closer than a thousandth of the box to the last kept one. Runs of sub-pixel
+/// segments otherwise rasterise as a dotted line.
+fn thin_path(x: &Array1<f64>, y: &Array1<f64>, bounds: [(f64, f64); 2]) -> Vec<(f64, f64)> {
+    let [(x_min, x_max), (y_min, y_max)] = bounds;
+    let (w, h) = (x_max - x_min, y_max - y_min);
+    let mut path: Vec<(f64, f64)> = Vec::new();
+    for (&x_i, &y_i) in x.iter().zip(y) {
+        let far = path
+            .last()
+            .is_none_or(|&(x_0, y_0)| ((x_i - x_0) / w).hypot((y_i - y_0) / h) >= 1e-3);
+        if far {
+            path.push((x_i, y_i));
+        }
+    }
+    path
+}
+
+/// The shaft and the two barbs of an arrow. The barbs are built in box-relative coordinates so
+/// they keep their shape however differently the axes are scaled.
+fn arrow_paths(
+    ((x0, y0), (x1, y1)): Segment,
+    [(x_min, x_max), (y_min, y_max)]: [(f64, f64); 2],
+) -> [Vec<(f64, f64)>; 2] {
+    let (w, h) = (x_max - x_min, y_max - y_min);
+    let (u, v) = ((x1 - x0) / w, (y1 - y0) / h);
+    let barb = |angle: f64| {
+        let (sin, cos) = angle.sin_cos();
+        (
+            x1 - 0.35 * (u * cos - v * sin) * w,
+            y1 - 0.35 * (u * sin + v * cos) * h,
+        )
+    };
+    [
+        vec![(x0, y0), (x1, y1)],
+        vec![barb(0.5), (x1, y1), barb(-0.5)],
+    ]
+}
+
+fn range<'a>(values: impl Iterator<Item = &'a Array1<f64>>) -> (f64, f64) {
+    values.fold((0., 0.), |(min, max), v| {
+        (
+            v.iter().cloned().fold(min, f64::min),
+            v.iter().cloned().fold(max, f64::max),
+        )
+    })
+}
+
+/// The span of the values with a 5% margin either side, widened around a single repeated value.
+fn padded_range<'a>(values: impl Iterator<Item = &'a Array1<f64>>) -> (f64, f64) {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (
+            v.iter().cloned().fold(min, f64::min),
+            v.iter().cloned().fold(max, f64::max),
+        )
+    });
+    let margin = if max > min {
+        0.05 * (max - min)
+    } else {
+        0.5 * max.abs().max(1.)
+    };
+    (min - margin, max + margin)
 }
\ No newline at end of file
@@ -0,0 +1,237 @@
+use ndarray::{Array1, Array2};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Scalar arithmetic shared by `f64` and `Dual`, so a right-hand side written once over `T: Real`
+/// gives plain values on `f64` and exact derivatives on `Dual`.
+pub trait Real:
+    Copy
+    + PartialOrd
+    + From<f64>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Add<f64, Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+{
+    fn value(self) -> f64;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn powf(self, n: f64) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn abs(self) -> Self;
+}
+
+impl Real for f64 {
+    fn value(self) -> f64 {
+        self
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn ln(self) -> Self {
+        f64::ln(self)
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn powf(self, n: f64) -> Self {
+        f64::powf(self, n)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        f64::powi(self, n)
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn abs(self) -> Self {
+        f64::abs(self)
+    }
+}
+
+/// `value + derivative * eps` with `eps^2 = 0`, carrying one directional derivative through
+/// every operation.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+pub struct Dual {
+    pub value: f64,
+    pub derivative: f64,
+}
+
+impl Dual {
+    pub fn new(value: f64, derivative: f64) -> Self {
+        Self { value, derivative }
+    }
+
+    pub fn constant(value: f64) -> Self {
+        Self::new(value, 0.)
+    }
+
+    pub fn variable(value: f64) -> Self {
+        Self::new(value, 1.)
+    }
+
+    /// Applies a function with value `f` and derivative `df` at `self.value`.
+    fn chain(self, f: f64, df: f64) -> Self {
+        Self::new(f, df * self.derivative)
+    }
+}
+
+impl From<f64> for Dual {
+    fn from(value: f64) -> Self {
+        Self::constant(value)
+    }
+}
+
+impl Add for Dual {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.value + rhs.value, self.derivative + rhs.derivative)
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.value - rhs.value, self.derivative - rhs.derivative)
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.value * rhs.value,
+            self.derivative * rhs.value + self.value * rhs.derivative,
+        )
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Self::new(
+            self.value / rhs.value,
+            (self.derivative * rhs.value - self.value * rhs.derivative) / (rhs.value * rhs.value),
+        )
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.value, -self.derivative)
+    }
+}
+
+impl Add<f64> for Dual {
+    type Output = Self;
+
+    fn add(self, rhs: f64) -> Self {
+        Self::new(self.value + rhs, self.derivative)
+    }
+}
+
+impl Sub<f64> for Dual {
+    type Output = Self;
+
+    fn sub(self, rhs: f64) -> Self {
+        Self::new(self.value - rhs, self.derivative)
+    }
+}
+
+impl Mul<f64> for Dual {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.value * rhs, self.derivative * rhs)
+    }
+}
+
+impl Div<f64> for Dual {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.value / rhs, self.derivative / rhs)
+    }
+}
+
+impl Real for Dual {
+    fn value(self) -> f64 {
+        self.value
+    }
+
+    fn exp(self) -> Self {
+        let e = self.value.exp();
+        self.chain(e, e)
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.value.ln(), 1. / self.value)
+    }
+
+    fn sqrt(self) -> Self {
+        let s = self.value.sqrt();
+        self.chain(s, 0.5 / s)
+    }
+
+    fn powf(self, n: f64) -> Self {
+        self.chain(self.value.powf(n), n * self.value.powf(n - 1.))
+    }
+
+    fn powi(self, n: i32) -> Self {
+        self.chain(self.value.powi(n), n as f64 * self.value.powi(n - 1))
+    }
+
+    fn sin(self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+
+    fn cos(self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+
+    fn abs(self) -> Self {
+        self.chain(self.value.abs(), self.value.signum())
+    }
+}
+
+/// Exact Jacobian of `f` at `x`, one forward pass per column.
+pub fn jacobian<F>(f: F, x: &Array1<f64>) -> Array2<f64>
+where
+    F: Fn(&Array1<Dual>) -> Array1<Dual>,
+{
+    let mut x_dual = x.mapv(Dual::constant);
+    let mut columns = Vec::with_capacity(x.len());
+    for j in 0..x.len() {
+        x_dual[j] = Dual::variable(x[j]);
+        columns.push(f(&x_dual).mapv(|f_i| f_i.derivative));
+        x_dual[j] = Dual::constant(x[j]);
+    }
+    let mut jac = Array2::zeros((columns.first().map_or(0, |c| c.len()), x.len()));
+    for (j, column) in columns.iter().enumerate() {
+        jac.column_mut(j).assign(column);
+    }
+    jac
+}
\ No newline at end of file
@@ -0,0 +1,296 @@
+use crate::linalg;
+use crate::sde_solvers::quantile_of_sorted;
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+#[derive(Copy, Clone)]
+pub struct McmcOptions {
+    /// Iterations kept after burn-in, before thinning. An ensemble iteration moves every walker.
+    pub n_samples: usize,
+    pub burn_in: usize,
+    /// Keeps every `thin`-th iteration.
+    pub thin: usize,
+    /// Starting proposal standard deviation as a fraction of each starting value.
+    pub initial_scale: f64,
+    /// Tunes the Metropolis proposal to the covariance of the chain during burn-in.
+    pub adapt: bool,
+    pub seed: u64,
+}
+
+impl Default for McmcOptions {
+    fn default() -> Self {
+        Self {
+            n_samples: 20_000,
+            burn_in: 5_000,
+            thin: 10,
+            initial_scale: 0.05,
+            adapt: true,
+            seed: 1,
+        }
+    }
+}
+
+/// Draws from a posterior, one row of `samples` per kept draw.
+pub struct Chain {
+    pub names: Vec<&'static str>,
+    pub samples: Array2<f64>,
+    pub log_density: Array1<f64>,
+    /// Fraction of proposals accepted after burn-in.
+    pub acceptance_rate: f64,
+}
+
+impl Chain {
+    pub fn column(&self, name: &str) -> usize {
+        self.names
+            .iter()
+            .position(|n| *n == name)
+            .unwrap_or_else(|| panic!("no parameter {} in the chain", name))
+    }
+
+    pub fn mean(&self) -> Array1<f64> {
+        self.samples.mean_axis(Axis(0)).unwrap()
+    }
+
+    pub fn quantile(&self, level: f64) -> Array1<f64> {
+        self.samples.map_axis(Axis(0), |x| {
+            let mut x = x.to_vec();
+            x.sort_by(f64::total_cmp);
+            quantile_of_sorted(&x, level)
+        })
+    }
+
+    pub fn print_summary(&self, title: &str) {
+        println!(
+            "{}: {} draws, acceptance rate {:.3}",
+            title,
+            self.samples.nrows(),
+            self.acceptance_rate
+        );
+        println!(
+            "{:>14} {:>14} {:>14} {:>30}",
+            "parameter", "mean", "median", "95% credible interval"
+        );
+        let (mean, median) = (self.mean(), self.quantile(0.5));
+        let (lower, upper) = (self.quantile(0.025), self.quantile(0.975));
+        for (k, name) in self.names.iter().enumerate() {
+            println!(
+                "{:>14} {:>14.6} {:>14.6} {:>30}",
+                name,
+                mean[k],
+                median[k],
+                format!("[{:.6}, {:.6}]", lower[k], upper[k])
+            );
+        }
+    }
+}
+
+/// Random-walk Metropolis-Hastings on `log_density` from the named starting values `parameters`,
+/// with Gaussian proposals. With `options.adapt` the proposal covariance is tuned during burn-in
+/// to `2.38^2 / d` times the covariance of the chain so far (Haario et al.'s adaptive Metropolis),
+/// then frozen so the kept draws come from a fixed Markov kernel.
+pub fn metropolis_hastings<F>(
+    log_density: F,
+    parameters: &[(&'static str, f64)],
+    options: &McmcOptions,
+) -> Chain
+where
+    F: Fn(&Array1<f64>) -> f64,
+{
+    let d = parameters.len();
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let mut x: Array1<f64> = parameters.iter().map(|(_, value)| *value).collect();
+    let mut log_p = log_density(&x);
+    assert!(
+        log_p.is_finite(),
+        "the starting point has zero posterior density"
+    );
+
+    let mut proposal = Array2::from_diag(&x.mapv(|x_j| {
+        let sd = options.initial_scale * x_j.abs().max(1e-8);
+        sd * sd
+    }));
+    let mut l = linalg::cholesky(&proposal).unwrap();
+    let (mut sum, mut outer) = (Array1::zeros(d), Array2::zeros((d, d)));
+
+    let n_kept = options.n_samples / options.thin;
+    let mut samples = Array2::zeros((n_kept, d));
+    let mut log_densities = Array1::zeros(n_kept);
+    let mut accepted = 0;
+
+    for iteration in 0..options.burn_in + options.n_samples {
+        let z: Array1<f64> = (0..d).map(|_| rng.sample(StandardNormal)).collect();
+        let y = &x + &l.dot(&z);
+        let log_q = log_density(&y);
+        if log_q.is_finite() && rng.gen::<f64>().ln() < log_q - log_p {
+            x = y;
+            log_p = log_q;
+            if iteration >= options.burn_in {
+                accepted += 1;
+            }
+        }
+
+        if iteration < options.burn_in {
+            sum += &x;
+            let x_col = x.view().insert_axis(Axis(1));
+            outer += &x_col.dot(&x_col.t());
+            let n = (iteration + 1) as f64;
+            if options.adapt && iteration + 1 >= 2 * d.max(50) && (iteration + 1) % 50 == 0 {
+                let mean = &sum / n;
+                let mean_col = mean.view().insert_axis(Axis(1));
+                let covariance = (&outer - &(n * mean_col.dot(&mean_col.t()))) / (n - 1.);
+                proposal = covariance * (2.38 * 2.38 / d as f64);
+                for j in 0..d {
+                    proposal[[j, j]] += 1e-12 * proposal[[j, j]].abs().max(1e-300);
+                }
+                if let Some(l_new) = linalg::cholesky(&proposal) {
+                    l = l_new;
+                }
+            }
+        } else if (iteration - options.burn_in) % options.thin == 0 {
+            let k = (iteration - options.burn_in) / options.thin;
+            if k < n_kept {
+                samples.row_mut(k).assign(&x);
+                log_densities[k] = log_p;
+            }
+        }
+    }
+
+    Chain {
+        names: parameters.iter().map(|(name, _)| *name).collect(),
+        samples,
+        log_density: log_densities,
+        acceptance_rate: accepted as f64 / options.n_samples as f64,
+    }
+}
+
+/// Goodman and Weare's affine-invariant ensemble sampler with the stretch move. `n_walkers`
+/// walkers start in a small ball around `parameters` and are moved one after another, each along
+/// the line through another walker. Every kept iteration contributes one draw per walker.
+pub fn ensemble<F>(
+    log_density: F,
+    parameters: &[(&'static str, f64)],
+    n_walkers: usize,
+    options: &McmcOptions,
+) -> Chain
+where
+    F: Fn(&Array1<f64>) -> f64,
+{
+    let d = parameters.len();
+    assert!(
+        n_walkers > d,
+        "the ensemble needs more walkers than parameters"
+    );
+    let a: f64 = 2.;
+    let mut rng = StdRng::seed_from_u64(options.seed);
+    let start: Array1<f64> = parameters.iter().map(|(_, value)| *value).collect();
+
+    let mut walkers = Array2::zeros((n_walkers, d));
+    let mut log_p = Array1::zeros(n_walkers);
+    for k in 0..n_walkers {
+        // retry until the walker lands where the density is positive
+        loop {
+            let x = start.mapv(|x_j| {
+                x_j + 1e-2
+                    * options.initial_scale
+                    * x_j.abs().max(1e-8)
+                    * rng.sample::<f64, _>(StandardNormal)
+            });
+            let log_q = log_density(&x);
+            if log_q.is_finite() {
+                walkers.row_mut(k).assign(&x);
+                log_p[k] = log_q;
+                break;
+            }
+        }
+    }
+
+    let n_kept = options.n_samples / options.thin;
+    let mut samples = Array2::zeros((n_kept * n_walkers, d));
+    let mut log_densities = Array1::zeros(n_kept * n_walkers);
+    let mut accepted = 0;
+
+    for iteration in 0..options.burn_in + options.n_samples {
+        for k in 0..n_walkers {
+            let j = (k + rng.gen_range(1..n_walkers)) % n_walkers;
+            let z = ((a - 1.) * rng.gen::<f64>() + 1.).powi(2) / a;
+            let y = &walkers.row(j) + &(z * (&walkers.row(k) - &walkers.row(j)));
+            let log_q = log_density(&y);
+            let log_ratio = (d as f64 - 1.) * z.ln() + log_q - log_p[k];
+            if log_q.is_finite() && rng.gen::<f64>().ln() < log_ratio {
+                walkers.row_mut(k).assign(&y);
+                log_p[k] = log_q;
+                if iteration >= options.burn_in {
+                    accepted += 1;
+                }
+            }
+        }
+
+        if iteration >= options.burn_in && (iteration - options.burn_in) % options.thin == 0 {
+            let kept = (iteration - options.burn_in) / options.thin;
+            if kept < n_kept {
+                for k in 0..n_walkers {
+                    samples
+                        .row_mut(kept * n_walkers + k)
+                        .assign(&walkers.row(k));
+                    log_densities[kept * n_walkers + k] = log_p[k];
+                }
+            }
+        }
+    }
+
+    Chain {
+        names: parameters.iter().map(|(name, _)| *name).collect(),
+        samples,
+        log_density: log_densities,
+        acceptance_rate: accepted as f64 / (options.n_samples * n_walkers) as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    /// Independent normals with means `(1, 2)` and standard deviations `(0.5, 2)`.
+    fn gaussian(x: &Array1<f64>) -> f64 {
+        -0.5 * (((x[0] - 1.) / 0.5).powi(2) + ((x[1] - 2.) / 2.).powi(2))
+    }
+
+    fn assert_matches_gaussian(chain: &Chain) {
+        let mean = chain.mean();
+        let sd = chain.samples.map_axis(Axis(0), |x| x.std(1.));
+        for (k, (mu, sigma)) in [(1., 0.5), (2., 2.)].into_iter().enumerate() {
+            assert!((mean[k] - mu).abs() < 0.15 * sigma, "mean {}", mean);
+            assert!((sd[k] / sigma - 1.).abs() < 0.15, "sd {}", sd);
+        }
+    }
+
+    #[test]
+    fn metropolis_hastings_samples_a_gaussian() {
+        let options = McmcOptions {
+            n_samples: 100_000,
+            ..McmcOptions::default()
+        };
+        let chain = metropolis_hastings(gaussian, &[("a", 0.5), ("b", 4.)], &options);
+        assert_eq!(chain.samples.nrows(), 10_000);
+        assert!(chain.acceptance_rate > 0.2 && chain.acceptance_rate < 0.7);
+        assert_matches_gaussian(&chain);
+    }
+
+    #[test]
+    fn ensemble_samples_a_gaussian() {
+        let chain = ensemble(
+            gaussian,
+            &[("a", 0.5), ("b", 4.)],
+            16,
+            &McmcOptions::default(),
+        );
+        assert_eq!(chain.samples.nrows(), 16 * 2_000);
+        assert_matches_gaussian(&chain);
+        assert_eq!(chain.column("b"), 1);
+        let median = chain.quantile(0.5);
+        assert!((median - array![1., 2.]).iter().all(|d| d.abs() < 0.3));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+mod mcmc;
+mod observations;
+mod optimisers;
+mod posterior;
+mod problem;
+
+pub use mcmc::{ensemble, metropolis_hastings, Chain, McmcOptions};
+pub use observations::Observations;
+pub use optimisers::{
+    levenberg_marquardt, nelder_mead, Bounds, LevenbergMarquardtOptions, Minimum, NelderMeadOptions,
+};
+pub use posterior::{Likelihood, Posterior, Prior};
+pub use problem::{Fit, FitProblem};
\ No newline at end of file
@@ -0,0 +1,81 @@
+use ndarray::{Array1, Array2};
+use std::fs;
+
+/// Observed time series: a column of times followed by one column per observed variable.
+pub struct Observations {
+    pub t: Array1<f64>,
+    pub names: Vec<String>,
+    /// One row per time, NaN where a value is missing.
+    pub y: Array2<f64>,
+}
+
+impl Observations {
+    pub fn from_csv(file_name: &str) -> Self {
+        let text = fs::read_to_string(file_name)
+            .unwrap_or_else(|e| panic!("cannot read observations from {}: {}", file_name, e));
+        Self::parse_csv(&text)
+    }
+
+    /// Parses comma-separated values with a header row naming the columns. The first column is
+    /// time, empty cells are missing values and lines starting with `#` are comments.
+    pub fn parse_csv(text: &str) -> Self {
+        let mut lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        let header: Vec<String> = lines
+            .next()
+            .expect("observations need a header row")
+            .split(',')
+            .map(|name| name.trim().to_string())
+            .collect();
+        assert!(
+            header.len() >= 2,
+            "observations need a time column and at least one variable"
+        );
+
+        let rows: Vec<Vec<f64>> = lines
+            .enumerate()
+            .map(|(i, line)| {
+                let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+                assert_eq!(
+                    cells.len(),
+                    header.len(),
+                    "row {} has {} cells, the header has {}",
+                    i + 1,
+                    cells.len(),
+                    header.len()
+                );
+                cells
+                    .iter()
+                    .map(|cell| {
+                        if cell.is_empty() {
+                            f64::NAN
+                        } else {
+                            cell.parse().unwrap_or_else(|_| {
+                                panic!("row {}: {} is not a number", i + 1, cell)
+                            })
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        assert!(
+            rows.windows(2).all(|w| w[0][0] < w[1][0]),
+            "observation times must be increasing"
+        );
+
+        Self {
+            t: rows.iter().map(|row| row[0]).collect(),
+            names: header[1..].to_vec(),
+            y: Array2::from_shape_fn((rows.len(), header.len() - 1), |(i, j)| rows[i][j + 1]),
+        }
+    }
+
+    pub fn column(&self, name: &str) -> usize {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .unwrap_or_else(|| panic!("no observed variable {}", name))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,349 @@
+use crate::linalg;
+use ndarray::{Array1, Array2};
+
+/// Box constraints, one `(lower, upper)` pair per unknown.
+pub type Bounds = [(f64, f64)];
+
+#[derive(Copy, Clone)]
+pub struct LevenbergMarquardtOptions {
+    pub max_iterations: usize,
+    /// Stops once an accepted step lowers the cost by less than this fraction.
+    pub tol: f64,
+    pub initial_damping: f64,
+}
+
+impl Default for LevenbergMarquardtOptions {
+    fn default() -> Self {
+        Self {
+            max_iterations: 200,
+            tol: 1e-10,
+            initial_damping: 1e-3,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct NelderMeadOptions {
+    pub max_iterations: usize,
+    /// Stops once the costs at the vertices of the simplex agree to this relative tolerance.
+    pub tol: f64,
+    /// Size of the starting simplex as a fraction of each bounded interval.
+    pub initial_step: f64,
+}
+
+impl Default for NelderMeadOptions {
+    fn default() -> Self {
+        Self {
+            max_iterations: 5000,
+            tol: 1e-12,
+            initial_step: 0.05,
+        }
+    }
+}
+
+/// The minimiser found, the number of iterations used and whether the tolerance was met.
+pub struct Minimum {
+    pub x: Array1<f64>,
+    pub cost: f64,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+fn clamp(x: &Array1<f64>, bounds: &Bounds) -> Array1<f64> {
+    Array1::from_shape_fn(x.len(), |j| x[j].clamp(bounds[j].0, bounds[j].1))
+}
+
+/// Sum of squares, infinite when a residual is not finite so failed model runs are rejected.
+fn sum_of_squares(r: &Array1<f64>) -> f64 {
+    let cost = r.dot(r);
+    if cost.is_finite() {
+        cost
+    } else {
+        f64::INFINITY
+    }
+}
+
+/// Minimises `|r(x)|^2` from `x_0` by Levenberg-Marquardt with Marquardt's diagonal scaling.
+/// The Jacobian is by finite differences and each step is projected back into `bounds`, with the
+/// unknowns held on a bound left out of the step.
+pub fn levenberg_marquardt<F>(
+    residuals: F,
+    x_0: &Array1<f64>,
+    bounds: &Bounds,
+    options: &LevenbergMarquardtOptions,
+) -> Minimum
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    let mut x = clamp(x_0, bounds);
+    let mut r = residuals(&x);
+    let mut cost = sum_of_squares(&r);
+    assert!(cost.is_finite(), "the model fails at the initial guess");
+    let mut damping = options.initial_damping;
+
+    for iteration in 1..=options.max_iterations {
+        let jac = linalg::jacobian(&residuals, &x);
+        let jac_t = jac.t();
+        let normal = jac_t.dot(&jac);
+        let gradient = jac_t.dot(&r);
+        // unknowns on a bound that the descent direction pushes outwards stay where they are
+        let active: Vec<bool> = (0..x.len())
+            .map(|j| {
+                (x[j] <= bounds[j].0 && gradient[j] > 0.)
+                    || (x[j] >= bounds[j].1 && gradient[j] < 0.)
+            })
+            .collect();
+        let rhs = Array1::from_shape_fn(x.len(), |j| if active[j] { 0. } else { -gradient[j] });
+
+        let mut accepted = None;
+        while damping < 1e12 {
+            let mut damped = normal.clone();
+            for j in 0..x.len() {
+                damped[[j, j]] += damping * normal[[j, j]].max(1e-12);
+            }
+            for j in (0..x.len()).filter(|j| active[*j]) {
+                damped.row_mut(j).fill(0.);
+                damped.column_mut(j).fill(0.);
+                damped[[j, j]] = 1.;
+            }
+            if let Some(delta) = linalg::solve(&damped, &rhs) {
+                let x_new = clamp(&(&x + &delta), bounds);
+                let r_new = residuals(&x_new);
+                let cost_new = sum_of_squares(&r_new);
+                if cost_new < cost {
+                    accepted = Some((x_new, r_new, cost_new));
+                    damping = (damping / 10.).max(1e-12);
+                    break;
+                }
+            }
+            damping *= 10.;
+        }
+
+        // no damping lowers the cost: a minimum, up to the finite-difference gradient
+        let Some((x_new, r_new, cost_new)) = accepted else {
+            return Minimum {
+                x,
+                cost,
+                iterations: iteration,
+                converged: true,
+            };
+        };
+        let decrease = (cost - cost_new) / cost.max(f64::MIN_POSITIVE);
+        x = x_new;
+        r = r_new;
+        cost = cost_new;
+        if decrease < options.tol {
+            return Minimum {
+                x,
+                cost,
+                iterations: iteration,
+                converged: true,
+            };
+        }
+    }
+
+    Minimum {
+        x,
+        cost,
+        iterations: options.max_iterations,
+        converged: false,
+    }
+}
+
+/// Minimises `cost(x)` from `x_0` with the Nelder-Mead simplex, keeping every vertex inside
+/// `bounds`.
+pub fn nelder_mead<F>(
+    cost: F,
+    x_0: &Array1<f64>,
+    bounds: &Bounds,
+    options: &NelderMeadOptions,
+) -> Minimum
+where
+    F: Fn(&Array1<f64>) -> f64,
+{
+    let n = x_0.len();
+    let cost = |x: &Array1<f64>| {
+        let c = cost(x);
+        if c.is_finite() {
+            c
+        } else {
+            f64::INFINITY
+        }
+    };
+
+    let x_0 = clamp(x_0, bounds);
+    let mut simplex = Array2::zeros((n + 1, n));
+    simplex.row_mut(0).assign(&x_0);
+    for j in 0..n {
+        let mut vertex = x_0.clone();
+        let step = options.initial_step * (bounds[j].1 - bounds[j].0);
+        // step away from the nearer bound so the vertex does not collapse onto it
+        vertex[j] += if x_0[j] + step <= bounds[j].1 {
+            step
+        } else {
+            -step
+        };
+        simplex.row_mut(j + 1).assign(&clamp(&vertex, bounds));
+    }
+    let mut costs: Vec<f64> = simplex
+        .rows()
+        .into_iter()
+        .map(|v| cost(&v.to_owned()))
+        .collect();
+
+    for iteration in 1..=options.max_iterations {
+        let mut order: Vec<usize> = (0..=n).collect();
+        order.sort_by(|&a, &b| costs[a].total_cmp(&costs[b]));
+        let (best, worst, second_worst) = (order[0], order[n], order[n - 1]);
+        if (costs[worst] - costs[best]).abs() <= options.tol * (costs[best].abs() + 1e-300) {
+            return Minimum {
+                x: simplex.row(best).to_owned(),
+                cost: costs[best],
+                iterations: iteration,
+                converged: true,
+            };
+        }
+
+        let centroid = order[..n]
+            .iter()
+            .fold(Array1::zeros(n), |c, &i| c + simplex.row(i))
+            / n as f64;
+        let towards = |s: f64| {
+            clamp(
+                &(&centroid + &(s * (&simplex.row(worst) - &centroid))),
+                bounds,
+            )
+        };
+
+        let reflected = towards(-1.);
+        let cost_reflected = cost(&reflected);
+        let replacement = if cost_reflected < costs[best] {
+            let expanded = towards(-2.);
+            let cost_expanded = cost(&expanded);
+            if cost_expanded < cost_reflected {
+                Some((expanded, cost_expanded))
+            } else {
+                Some((reflected, cost_reflected))
+            }
+        } else if cost_reflected < costs[second_worst] {
+            Some((reflected, cost_reflected))
+        } else {
+            let contracted = if cost_reflected < costs[worst] {
+                towards(-0.5)
+            } else {
+                towards(0.5)
+            };
+            let cost_contracted = cost(&contracted);
+            (cost_contracted < costs[worst].min(cost_reflected))
+                .then_some((contracted, cost_contracted))
+        };
+
+        match replacement {
+            Some((vertex, c)) => {
+                simplex.row_mut(worst).assign(&vertex);
+                costs[worst] = c;
+            }
+            None => {
+                // shrink towards the best vertex
+                let x_best = simplex.row(best).to_owned();
+                for i in order[1..].iter().copied() {
+                    let vertex = &x_best + &(0.5 * (&simplex.row(i) - &x_best));
+                    costs[i] = cost(&vertex);
+                    simplex.row_mut(i).assign(&vertex);
+                }
+            }
+        }
+    }
+
+    let best = (0..=n)
+        .min_by(|&a, &b| costs[a].total_cmp(&costs[b]))
+        .unwrap();
+    Minimum {
+        x: simplex.row(best).to_owned(),
+        cost: costs[best],
+        iterations: options.max_iterations,
+        converged: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    const UNBOUNDED: [(f64, f64); 2] = [(-1e6, 1e6), (-1e6, 1e6)];
+
+    /// Residuals of the straight line `y = 2 x + 1` through noisy points, whose least-squares fit
+    /// is `(2.03, 0.98)` by the normal equations.
+    fn line_residuals(x: &Array1<f64>) -> Array1<f64> {
+        let (t, y) = (array![0., 1., 2., 3.], array![1.1, 2.8, 5.1, 7.1]);
+        y - (x[0] * &t + x[1])
+    }
+
+    #[test]
+    fn levenberg_marquardt_solves_linear_least_squares() {
+        let minimum = levenberg_marquardt(
+            line_residuals,
+            &array![0., 0.],
+            &UNBOUNDED,
+            &LevenbergMarquardtOptions::default(),
+        );
+        assert!(minimum.converged);
+        assert!((minimum.x[0] - 2.03).abs() < 1e-6);
+        assert!((minimum.x[1] - 0.98).abs() < 1e-6);
+    }
+
+    #[test]
+    fn levenberg_marquardt_finds_the_rosenbrock_minimum() {
+        let residuals = |x: &Array1<f64>| array![1. - x[0], 10. * (x[1] - x[0] * x[0])];
+        let minimum = levenberg_marquardt(
+            residuals,
+            &array![-1.2, 1.],
+            &UNBOUNDED,
+            &LevenbergMarquardtOptions::default(),
+        );
+        assert!((minimum.x[0] - 1.).abs() < 1e-5);
+        assert!((minimum.x[1] - 1.).abs() < 1e-5);
+        assert!(minimum.cost < 1e-10);
+    }
+
+    #[test]
+    fn levenberg_marquardt_stops_on_an_active_bound() {
+        let minimum = levenberg_marquardt(
+            line_residuals,
+            &array![0., 0.],
+            &[(0., 1.5), (-1e6, 1e6)],
+            &LevenbergMarquardtOptions::default(),
+        );
+        assert!((minimum.x[0] - 1.5).abs() < 1e-12);
+        assert!((minimum.x[1] - 1.775).abs() < 1e-6);
+    }
+
+    #[test]
+    fn nelder_mead_minimises_a_quadratic() {
+        let cost = |x: &Array1<f64>| (x[0] - 3.).powi(2) + 2. * (x[1] + 1.).powi(2) + 5.;
+        let minimum = nelder_mead(
+            cost,
+            &array![0., 0.],
+            &[(-10., 10.), (-10., 10.)],
+            &NelderMeadOptions::default(),
+        );
+        assert!(minimum.converged);
+        assert!((minimum.x[0] - 3.).abs() < 1e-4);
+        assert!((minimum.x[1] + 1.).abs() < 1e-4);
+        assert!((minimum.cost - 5.).abs() < 1e-8);
+    }
+
+    #[test]
+    fn nelder_mead_agrees_with_levenberg_marquardt() {
+        let cost = |x: &Array1<f64>| sum_of_squares(&line_residuals(x));
+        let minimum = nelder_mead(
+            cost,
+            &array![1., 1.],
+            &[(-10., 10.), (-10., 10.)],
+            &NelderMeadOptions::default(),
+        );
+        assert!((minimum.x[0] - 2.03).abs() < 1e-4);
+        assert!((minimum.x[1] - 0.98).abs() < 1e-4);
+    }
+}
\ No newline at end of file
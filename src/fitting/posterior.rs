@@ -0,0 +1,261 @@
+use super::mcmc::{ensemble, metropolis_hastings, Chain, McmcOptions};
+use super::FitProblem;
+use crate::ode_solvers::RightHandSide;
+use crate::sde_solvers::EnsembleStatistics;
+use ndarray::{Array1, Array2};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, Gamma, Normal, Poisson};
+use std::f64::consts::PI;
+
+/// How an observation scatters about the model's value `mu`.
+#[derive(Copy, Clone, Debug)]
+pub enum Likelihood {
+    Gaussian {
+        sigma: f64,
+    },
+    /// Counts with variance equal to the mean.
+    Poisson,
+    /// Overdispersed counts with variance `mu + mu^2 / dispersion`.
+    NegativeBinomial {
+        dispersion: f64,
+    },
+}
+
+impl Likelihood {
+    pub fn log_likelihood(&self, mu: f64, y: f64) -> f64 {
+        match *self {
+            Likelihood::Gaussian { sigma } => {
+                let z = (y - mu) / sigma;
+                -0.5 * z * z - sigma.ln() - 0.5 * (2. * PI).ln()
+            }
+            Likelihood::Poisson => {
+                if mu <= 0. {
+                    return if y == 0. && mu == 0. {
+                        0.
+                    } else {
+                        f64::NEG_INFINITY
+                    };
+                }
+                y * mu.ln() - mu - ln_gamma(y + 1.)
+            }
+            Likelihood::NegativeBinomial { dispersion: k } => {
+                if mu <= 0. {
+                    return if y == 0. && mu == 0. {
+                        0.
+                    } else {
+                        f64::NEG_INFINITY
+                    };
+                }
+                ln_gamma(y + k) - ln_gamma(k) - ln_gamma(y + 1.)
+                    + k * (k / (k + mu)).ln()
+                    + y * (mu / (k + mu)).ln()
+            }
+        }
+    }
+
+    /// A random observation with model value `mu`.
+    pub fn sample<G: Rng>(&self, mu: f64, rng: &mut G) -> f64 {
+        match *self {
+            Likelihood::Gaussian { sigma } => Normal::new(mu, sigma).unwrap().sample(rng),
+            _ if mu <= 0. => 0.,
+            Likelihood::Poisson => Poisson::new(mu).unwrap().sample(rng),
+            // a Poisson count whose mean is gamma distributed
+            Likelihood::NegativeBinomial { dispersion: k } => {
+                let rate = Gamma::new(k, mu / k).unwrap().sample(rng);
+                if rate > 0. {
+                    Poisson::new(rate).unwrap().sample(rng)
+                } else {
+                    0.
+                }
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Prior {
+    /// Flat over the parameter's bounds.
+    Uniform,
+    Normal {
+        mean: f64,
+        sd: f64,
+    },
+    /// `ln x` is normal with mean `mu` and standard deviation `sigma`.
+    LogNormal {
+        mu: f64,
+        sigma: f64,
+    },
+}
+
+impl Prior {
+    /// Log density up to a constant.
+    pub fn log_density(&self, x: f64) -> f64 {
+        match *self {
+            Prior::Uniform => 0.,
+            Prior::Normal { mean, sd } => -0.5 * ((x - mean) / sd).powi(2),
+            Prior::LogNormal { mu, sigma } => {
+                if x <= 0. {
+                    f64::NEG_INFINITY
+                } else {
+                    -0.5 * ((x.ln() - mu) / sigma).powi(2) - x.ln()
+                }
+            }
+        }
+    }
+}
+
+/// The posterior of the free unknowns of a `FitProblem`, whose bounds are hard limits on top of
+/// the priors.
+pub struct Posterior<'a, R: RightHandSide> {
+    problem: &'a FitProblem<'a, R>,
+    likelihood: Likelihood,
+    priors: Vec<Prior>,
+}
+
+impl<'a, R: RightHandSide> Posterior<'a, R> {
+    pub fn new(problem: &'a FitProblem<'a, R>, likelihood: Likelihood) -> Self {
+        Self {
+            problem,
+            likelihood,
+            priors: vec![Prior::Uniform; problem.names().len()],
+        }
+    }
+
+    pub fn with_prior(mut self, name: &str, prior: Prior) -> Self {
+        let k = self
+            .problem
+            .names()
+            .iter()
+            .position(|n| *n == name)
+            .unwrap_or_else(|| panic!("{} is not a free unknown", name));
+        self.priors[k] = prior;
+        self
+    }
+
+    /// Log posterior density up to a constant, `-inf` outside the bounds or where the model
+    /// fails.
+    pub fn log_density(&self, theta: &Array1<f64>) -> f64 {
+        let inside = self
+            .problem
+            .bounds()
+            .iter()
+            .zip(theta)
+            .all(|((lower, upper), x)| x >= lower && x <= upper);
+        if !inside {
+            return f64::NEG_INFINITY;
+        }
+        let log_prior: f64 = self
+            .priors
+            .iter()
+            .zip(theta)
+            .map(|(prior, x)| prior.log_density(*x))
+            .sum();
+        if !log_prior.is_finite() {
+            return f64::NEG_INFINITY;
+        }
+
+        let y = self.problem.trajectory(theta);
+        let observed = &self.problem.observations().y;
+        let mut log_likelihood = 0.;
+        for i in 0..observed.nrows() {
+            for &(column, component) in self.problem.observed() {
+                let y_obs = observed[[i, column]];
+                if y_obs.is_finite() {
+                    log_likelihood += self.likelihood.log_likelihood(y[[i, component]], y_obs);
+                }
+            }
+        }
+        let log_p = log_prior + log_likelihood;
+        if log_p.is_nan() {
+            f64::NEG_INFINITY
+        } else {
+            log_p
+        }
+    }
+
+    fn start(&self, theta_0: &Array1<f64>) -> Vec<(&'static str, f64)> {
+        self.problem
+            .names()
+            .into_iter()
+            .zip(theta_0.iter().cloned())
+            .collect()
+    }
+
+    /// Adaptive Metropolis-Hastings from `theta_0`, e.g. a least-squares estimate.
+    pub fn metropolis_hastings(&self, theta_0: &Array1<f64>, options: &McmcOptions) -> Chain {
+        metropolis_hastings(
+            |theta| self.log_density(theta),
+            &self.start(theta_0),
+            options,
+        )
+    }
+
+    pub fn ensemble(
+        &self,
+        theta_0: &Array1<f64>,
+        n_walkers: usize,
+        options: &McmcOptions,
+    ) -> Chain {
+        ensemble(
+            |theta| self.log_density(theta),
+            &self.start(theta_0),
+            n_walkers,
+            options,
+        )
+    }
+
+    /// Posterior predictive distribution of the observed variables at times `t`: the model run
+    /// with `n_draws` draws from `chain`, with observation noise from the likelihood added. One
+    /// column per observed variable, in the order they were observed.
+    pub fn predictive(
+        &self,
+        chain: &Chain,
+        t: &Array1<f64>,
+        n_draws: usize,
+        levels: &[f64],
+        seed: u64,
+    ) -> EnsembleStatistics {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let observed = self.problem.observed();
+        let n = chain.samples.nrows();
+        let paths: Vec<Array2<f64>> = (0..n_draws)
+            .map(|draw| {
+                let theta = chain.samples.row(draw * n / n_draws).to_owned();
+                let y = self.problem.solution(&theta, t);
+                Array2::from_shape_fn((t.len(), observed.len()), |(i, m)| {
+                    self.likelihood.sample(y[[i, observed[m].1]], &mut rng)
+                })
+            })
+            .collect();
+        EnsembleStatistics::from_paths(&paths, levels)
+    }
+}
+
+/// Lanczos approximation to `ln Gamma(x)` for `x > 0`, good to about 1e-15.
+#[allow(clippy::excessive_precision)]
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.;
+    const COEFFICIENTS: [f64; 9] = [
+        0.99999999999980993,
+        676.5203681218851,
+        -1259.1392167224028,
+        771.32342877765313,
+        -176.61502916214059,
+        12.507343278686905,
+        -0.13857109526572012,
+        9.9843695780195716e-6,
+        1.5056327351493116e-7,
+    ];
+    if x < 0.5 {
+        // reflection
+        return (PI / (PI * x).sin()).ln() - ln_gamma(1. - x);
+    }
+    let x = x - 1.;
+    let t = x + G + 0.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |s, (i, c)| s + c / (x + i as f64 + 1.));
+    0.5 * (2. * PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
\ No newline at end of file
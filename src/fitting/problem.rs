@@ -0,0 +1,369 @@
+use super::optimisers::{
+    levenberg_marquardt, nelder_mead, LevenbergMarquardtOptions, Minimum, NelderMeadOptions,
+};
+use super::Observations;
+use crate::linalg;
+use crate::ode_solvers::{ODESolver, RightHandSide};
+use ndarray::{Array1, Array2};
+
+#[derive(Copy, Clone)]
+enum Unknown {
+    /// Index into the model's parameters.
+    Parameter(usize),
+    /// Index into the initial condition.
+    InitialCondition(usize),
+}
+
+/// Weighted least-squares fit of a model's trajectory to observations. The model starts from
+/// `y_0` at the first observed time and is integrated with fixed steps of about `h`, then
+/// interpolated linearly to the observed times.
+pub struct FitProblem<'a, R: RightHandSide> {
+    ode_solver: ODESolver<'a>,
+    rhs: &'a R,
+    observations: &'a Observations,
+    y_0: Array1<f64>,
+    h: f64,
+    /// The state component each observed column is compared with.
+    observed: Vec<(usize, usize)>,
+    /// `1 / sigma^2` for each observed column.
+    weights: Array1<f64>,
+    free: Vec<(&'static str, Unknown, (f64, f64))>,
+}
+
+impl<'a, R: RightHandSide> FitProblem<'a, R> {
+    pub fn new(
+        ode_solver: ODESolver<'a>,
+        rhs: &'a R,
+        observations: &'a Observations,
+        y_0: &Array1<f64>,
+        h: f64,
+    ) -> Self {
+        Self {
+            ode_solver,
+            rhs,
+            observations,
+            y_0: y_0.clone(),
+            h,
+            observed: Vec::new(),
+            weights: Array1::ones(observations.names.len()),
+            free: Vec::new(),
+        }
+    }
+
+    /// Compares the observed column `name` with the state component `component`.
+    pub fn observe(mut self, name: &str, component: usize) -> Self {
+        self.observed
+            .push((self.observations.column(name), component));
+        self
+    }
+
+    /// Weights the observed column `name` by `1 / sigma^2`.
+    pub fn with_sigma(mut self, name: &str, sigma: f64) -> Self {
+        self.weights[self.observations.column(name)] = 1. / (sigma * sigma);
+        self
+    }
+
+    /// Estimates the model parameter `name` within `bounds`, starting from the model's value.
+    pub fn free(mut self, name: &'static str, bounds: (f64, f64)) -> Self {
+        let k = self
+            .rhs
+            .parameters()
+            .iter()
+            .position(|(n, _)| *n == name)
+            .unwrap_or_else(|| panic!("the model has no parameter {}", name));
+        self.free.push((name, Unknown::Parameter(k), bounds));
+        self
+    }
+
+    /// Estimates the initial value of `component`, called `name`, within `bounds`, starting from
+    /// `y_0`.
+    pub fn free_initial_condition(
+        mut self,
+        name: &'static str,
+        component: usize,
+        bounds: (f64, f64),
+    ) -> Self {
+        self.free
+            .push((name, Unknown::InitialCondition(component), bounds));
+        self
+    }
+
+    /// The starting values of the free unknowns.
+    pub fn initial_guess(&self) -> Array1<f64> {
+        let p = self.rhs.parameter_values();
+        self.free
+            .iter()
+            .map(|(_, unknown, _)| match *unknown {
+                Unknown::Parameter(k) => p[k],
+                Unknown::InitialCondition(j) => self.y_0[j],
+            })
+            .collect()
+    }
+
+    /// The model's trajectory at the observed times with the free unknowns set to `theta`, one
+    /// row per time.
+    pub fn trajectory(&self, theta: &Array1<f64>) -> Array2<f64> {
+        self.solution(theta, &self.observations.t)
+    }
+
+    /// Like `trajectory`, at any times `t_out` from the first observed time on.
+    pub fn solution(&self, theta: &Array1<f64>, t_out: &Array1<f64>) -> Array2<f64> {
+        let (mut p, mut y_0) = (self.rhs.parameter_values(), self.y_0.clone());
+        for ((_, unknown, _), value) in self.free.iter().zip(theta) {
+            match *unknown {
+                Unknown::Parameter(k) => p[k] = *value,
+                Unknown::InitialCondition(j) => y_0[j] = *value,
+            }
+        }
+
+        let t_0 = self.observations.t[0];
+        let t_n = t_out.fold(t_0, |t_n, t| t_n.max(*t));
+        let n_steps = (((t_n - t_0) / self.h).ceil() as usize).max(1);
+        let h = (t_n - t_0) / n_steps as f64;
+        let t = Array1::linspace(t_0, t_n, n_steps + 1);
+        let y = self
+            .ode_solver
+            .solve(|t, y| self.rhs.eval(t, y, &p), h, n_steps, &t, &y_0);
+
+        Array2::from_shape_fn((t_out.len(), y_0.len()), |(i, j)| {
+            assert!(
+                t_out[i] >= t_0,
+                "the model starts at the first observed time"
+            );
+            let s = (t_out[i] - t_0) / h;
+            let k = (s.floor() as usize).min(n_steps - 1);
+            let s = s - k as f64;
+            (1. - s) * y[[k, j]] + s * y[[k + 1, j]]
+        })
+    }
+
+    /// Weighted residuals `sqrt(w) (model - observed)`, with missing observations contributing
+    /// zero.
+    pub fn residuals(&self, theta: &Array1<f64>) -> Array1<f64> {
+        let y = self.trajectory(theta);
+        let observed = &self.observations.y;
+        let mut r = Array1::zeros(observed.nrows() * self.observed.len());
+        for i in 0..observed.nrows() {
+            for (m, &(column, component)) in self.observed.iter().enumerate() {
+                let y_obs = observed[[i, column]];
+                if y_obs.is_finite() {
+                    r[i * self.observed.len() + m] =
+                        self.weights[column].sqrt() * (y[[i, component]] - y_obs);
+                }
+            }
+        }
+        r
+    }
+
+    pub(super) fn observations(&self) -> &Observations {
+        self.observations
+    }
+
+    /// `(observed column, state component)` pairs.
+    pub(super) fn observed(&self) -> &[(usize, usize)] {
+        &self.observed
+    }
+
+    pub fn levenberg_marquardt(&self, options: &LevenbergMarquardtOptions) -> Fit {
+        assert!(!self.free.is_empty(), "nothing to fit");
+        let minimum = levenberg_marquardt(
+            |theta| self.residuals(theta),
+            &self.initial_guess(),
+            &self.bounds(),
+            options,
+        );
+        self.report(minimum)
+    }
+
+    pub fn nelder_mead(&self, options: &NelderMeadOptions) -> Fit {
+        assert!(!self.free.is_empty(), "nothing to fit");
+        let minimum = nelder_mead(
+            |theta| {
+                let r = self.residuals(theta);
+                r.dot(&r)
+            },
+            &self.initial_guess(),
+            &self.bounds(),
+            options,
+        );
+        self.report(minimum)
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.free.iter().map(|(name, _, _)| *name).collect()
+    }
+
+    pub fn bounds(&self) -> Vec<(f64, f64)> {
+        self.free.iter().map(|(_, _, bounds)| *bounds).collect()
+    }
+
+    /// Residuals and the linearised covariance `s^2 (J^T J)^-1` at the minimum, with `J` the
+    /// Jacobian of the weighted residuals and `s^2` their variance.
+    fn report(&self, minimum: Minimum) -> Fit {
+        let theta = minimum.x;
+        let n_obs: usize = self
+            .observed
+            .iter()
+            .map(|&(column, _)| {
+                self.observations
+                    .y
+                    .column(column)
+                    .iter()
+                    .filter(|y| y.is_finite())
+                    .count()
+            })
+            .sum();
+        let degrees_of_freedom = n_obs.saturating_sub(theta.len());
+
+        let jac = linalg::jacobian(|theta| self.residuals(theta), &theta);
+        let variance = minimum.cost / degrees_of_freedom.max(1) as f64;
+        let covariance = linalg::LuDecomposition::new(&jac.t().dot(&jac))
+            .map(|lu| {
+                let n = theta.len();
+                let mut inverse = Array2::zeros((n, n));
+                for j in 0..n {
+                    let mut e_j = Array1::zeros(n);
+                    e_j[j] = 1.;
+                    inverse.column_mut(j).assign(&lu.solve(&e_j));
+                }
+                inverse * variance
+            })
+            .unwrap_or_else(|| Array2::from_elem((theta.len(), theta.len()), f64::NAN));
+
+        let y = self.trajectory(&theta);
+        let observed = &self.observations.y;
+        let residuals = Array2::from_shape_fn((observed.nrows(), self.observed.len()), |(i, m)| {
+            let (column, component) = self.observed[m];
+            y[[i, component]] - observed[[i, column]]
+        });
+
+        Fit {
+            names: self.names(),
+            estimates: theta,
+            residuals,
+            cost: minimum.cost,
+            covariance,
+            degrees_of_freedom,
+            iterations: minimum.iterations,
+            converged: minimum.converged,
+        }
+    }
+}
+
+/// Estimates with their uncertainty.
+pub struct Fit {
+    pub names: Vec<&'static str>,
+    pub estimates: Array1<f64>,
+    /// Model minus observation for each observed time and fitted column, NaN where missing.
+    pub residuals: Array2<f64>,
+    /// Weighted sum of squared residuals.
+    pub cost: f64,
+    pub covariance: Array2<f64>,
+    pub degrees_of_freedom: usize,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+impl Fit {
+    pub fn standard_errors(&self) -> Array1<f64> {
+        self.covariance.diag().mapv(f64::sqrt)
+    }
+
+    /// Two-sided Wald intervals `estimate +- t se` at confidence `level`, with `t` the Student
+    /// quantile for the residual degrees of freedom.
+    pub fn confidence_intervals(&self, level: f64) -> Vec<(f64, f64)> {
+        let t = student_t_quantile(0.5 + 0.5 * level, self.degrees_of_freedom.max(1) as f64);
+        self.estimates
+            .iter()
+            .zip(self.standard_errors())
+            .map(|(estimate, se)| (estimate - t * se, estimate + t * se))
+            .collect()
+    }
+
+    pub fn print_report(&self, title: &str) {
+        println!(
+            "{}: cost {:.6} on {} degrees of freedom after {} iterations{}",
+            title,
+            self.cost,
+            self.degrees_of_freedom,
+            self.iterations,
+            if self.converged {
+                ""
+            } else {
+                " (not converged)"
+            }
+        );
+        println!(
+            "{:>14} {:>14} {:>14} {:>30}",
+            "parameter", "estimate", "std. error", "95% interval"
+        );
+        let intervals = self.confidence_intervals(0.95);
+        for (k, name) in self.names.iter().enumerate() {
+            println!(
+                "{:>14} {:>14.6} {:>14.6} {:>30}",
+                name,
+                self.estimates[k],
+                self.standard_errors()[k],
+                format!("[{:.6}, {:.6}]", intervals[k].0, intervals[k].1)
+            );
+        }
+    }
+}
+
+/// Acklam's rational approximation to the standard normal quantile, relative error below 1.2e-9.
+#[allow(clippy::excessive_precision)]
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.383577518672690e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    };
+    if p < 0.02425 {
+        tail((-2. * p.ln()).sqrt())
+    } else if p > 1. - 0.02425 {
+        -tail((-2. * (1. - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    }
+}
+
+/// Student t quantile by the Cornish-Fisher expansion about the normal one, good to about 1e-3
+/// from four degrees of freedom.
+fn student_t_quantile(p: f64, dof: f64) -> f64 {
+    let z = normal_quantile(p);
+    let (z3, z5, z7) = (z.powi(3), z.powi(5), z.powi(7));
+    z + (z3 + z) / (4. * dof)
+        + (5. * z5 + 16. * z3 + 3. * z) / (96. * dof.powi(2))
+        + (3. * z7 + 19. * z5 + 17. * z3 - 15. * z) / (384. * dof.powi(3))
+}
\ No newline at end of file
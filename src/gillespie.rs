@@ -0,0 +1,342 @@
+use ndarray::{array, Array1, Array2};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, Exp1, Poisson};
+
+type Propensity<'a> = Box<dyn Fn(&Array1<f64>) -> f64 + 'a>;
+
+/// A reaction channel firing at rate `propensity(x)` and changing the counts by `stoichiometry`.
+pub struct Reaction<'a> {
+    pub name: &'static str,
+    propensity: Propensity<'a>,
+    pub stoichiometry: Array1<f64>,
+}
+
+impl<'a> Reaction<'a> {
+    pub fn propensity(&self, x: &Array1<f64>) -> f64 {
+        let a = (self.propensity)(x);
+        assert!(
+            a >= 0.,
+            "reaction {} has negative propensity {}",
+            self.name,
+            a
+        );
+        a
+    }
+}
+
+pub struct ReactionNetwork<'a> {
+    pub species: Vec<&'static str>,
+    pub reactions: Vec<Reaction<'a>>,
+}
+
+impl<'a> ReactionNetwork<'a> {
+    pub fn new(species: Vec<&'static str>) -> Self {
+        Self {
+            species,
+            reactions: Vec::new(),
+        }
+    }
+
+    pub fn reaction<P>(
+        mut self,
+        name: &'static str,
+        propensity: P,
+        stoichiometry: Array1<f64>,
+    ) -> Self
+    where
+        P: Fn(&Array1<f64>) -> f64 + 'a,
+    {
+        assert_eq!(
+            stoichiometry.len(),
+            self.species.len(),
+            "reaction {} must change every species",
+            name
+        );
+        self.reactions.push(Reaction {
+            name,
+            propensity: Box::new(propensity),
+            stoichiometry,
+        });
+        self
+    }
+
+    fn propensities(&self, x: &Array1<f64>) -> Vec<f64> {
+        self.reactions.iter().map(|r| r.propensity(x)).collect()
+    }
+
+    /// Individual-level reactions of `rabies_pest1`: the logistic term `r (S + I) (1 - S / k)`
+    /// splits into births and crowding deaths of susceptibles.
+    pub fn rabies((r, beta, u, k): (f64, f64, f64, f64)) -> Self {
+        Self::new(vec!["susceptible", "infective"])
+            .reaction("birth", move |x| r * (x[0] + x[1]), array![1., 0.])
+            .reaction(
+                "crowding",
+                move |x| r * (x[0] + x[1]) * x[0] / k,
+                array![-1., 0.],
+            )
+            .reaction("infection", move |x| beta * x[0] * x[1], array![-1., 1.])
+            .reaction("death", move |x| u * x[1], array![0., -1.])
+    }
+
+    /// `rabies_pest2`, where both classes are also culled at rate `c`.
+    pub fn rabies_with_culling((r, beta, u, k, c): (f64, f64, f64, f64, f64)) -> Self {
+        Self::rabies((r, beta, u, k))
+            .reaction("culling susceptible", move |x| c * x[0], array![-1., 0.])
+            .reaction("culling infective", move |x| c * x[1], array![0., -1.])
+    }
+
+    /// `rabies_pest3`, where a fraction `v` of the contacts is prevented by vaccination.
+    pub fn rabies_with_vaccination((r, beta, u, k, v): (f64, f64, f64, f64, f64)) -> Self {
+        assert!(
+            (0. ..=1.).contains(&v),
+            "vaccination coverage must be a fraction, got {}",
+            v
+        );
+        Self::rabies((r, beta * (1. - v), u, k))
+    }
+
+    pub fn lotka_volterra((alpha1, beta1, alpha2, beta2): (f64, f64, f64, f64)) -> Self {
+        Self::new(vec!["preys", "predator"])
+            .reaction("prey birth", move |x| alpha1 * x[0], array![1., 0.])
+            .reaction("predation", move |x| beta1 * x[0] * x[1], array![-1., 0.])
+            .reaction("predator death", move |x| alpha2 * x[1], array![0., -1.])
+            .reaction(
+                "predator birth",
+                move |x| beta2 * x[0] * x[1],
+                array![0., 1.],
+            )
+    }
+}
+
+/// A piecewise constant sample path: the counts are `x[i]` on `[t[i], t[i + 1])`.
+pub struct Trajectory {
+    pub t: Vec<f64>,
+    pub x: Vec<Array1<f64>>,
+}
+
+impl Trajectory {
+    /// The counts at each time of `t`, one row per time point.
+    pub fn resample(&self, t: &Array1<f64>) -> Array2<f64> {
+        let mut x = Array2::zeros((t.len(), self.x[0].len()));
+        for (mut row, t_i) in x.rows_mut().into_iter().zip(t) {
+            let i = self.t.partition_point(|t_j| t_j <= t_i).max(1) - 1;
+            row.assign(&self.x[i]);
+        }
+        x
+    }
+
+    /// The first time `species` reaches zero, if it does.
+    pub fn extinction_time(&self, species: usize) -> Option<f64> {
+        self.x
+            .iter()
+            .position(|x| x[species] <= 0.)
+            .map(|i| self.t[i])
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum SSAMethod {
+    /// Gillespie's direct method.
+    Direct,
+    /// Gibson and Bruck's next reaction method, reusing the waiting times of the channels
+    /// that did not fire.
+    NextReaction,
+    /// Poisson leaps of at most the given length, halved while any count would turn negative.
+    TauLeaping(f64),
+}
+
+#[derive(Copy, Clone)]
+pub struct SSASolver {
+    method: SSAMethod,
+    seed: u64,
+}
+
+impl SSASolver {
+    pub fn new(method: SSAMethod, seed: u64) -> Self {
+        Self { method, seed }
+    }
+
+    pub fn simulate(self, network: &ReactionNetwork, x_0: &Array1<f64>, t_end: f64) -> Trajectory {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        self.simulate_with_rng(network, x_0, t_end, &mut rng)
+    }
+
+    /// `n_paths` independent paths from one RNG stream seeded with the solver's seed.
+    pub fn trajectories(
+        self,
+        network: &ReactionNetwork,
+        x_0: &Array1<f64>,
+        t_end: f64,
+        n_paths: usize,
+    ) -> Vec<Trajectory> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        (0..n_paths)
+            .map(|_| self.simulate_with_rng(network, x_0, t_end, &mut rng))
+            .collect()
+    }
+
+    /// Runs until `t_end` or until no reaction can fire; the path always ends with a point at
+    /// `t_end`.
+    pub fn simulate_with_rng<R: Rng>(
+        self,
+        network: &ReactionNetwork,
+        x_0: &Array1<f64>,
+        t_end: f64,
+        rng: &mut R,
+    ) -> Trajectory {
+        let mut path = Trajectory {
+            t: vec![0.],
+            x: vec![x_0.clone()],
+        };
+        match self.method {
+            SSAMethod::Direct => direct(network, t_end, rng, &mut path),
+            SSAMethod::NextReaction => next_reaction(network, t_end, rng, &mut path),
+            SSAMethod::TauLeaping(tau) => tau_leaping(network, t_end, tau, rng, &mut path),
+        }
+        if *path.t.last().unwrap() < t_end {
+            let x_end = path.x.last().unwrap().clone();
+            path.t.push(t_end);
+            path.x.push(x_end);
+        }
+        path
+    }
+}
+
+fn direct<R: Rng>(network: &ReactionNetwork, t_end: f64, rng: &mut R, path: &mut Trajectory) {
+    let mut t = 0.;
+    let mut x = path.x[0].clone();
+    loop {
+        let a = network.propensities(&x);
+        let a_0: f64 = a.iter().sum();
+        if a_0 == 0. {
+            break;
+        }
+        t += rng.sample::<f64, _>(Exp1) / a_0;
+        if t > t_end {
+            break;
+        }
+        let target = rng.gen::<f64>() * a_0;
+        let mut cumulative = 0.;
+        let j = a
+            .iter()
+            .position(|a_j| {
+                cumulative += a_j;
+                cumulative > target
+            })
+            .unwrap_or(a.len() - 1);
+        x += &network.reactions[j].stoichiometry;
+        path.t.push(t);
+        path.x.push(x.clone());
+    }
+}
+
+fn next_reaction<R: Rng>(
+    network: &ReactionNetwork,
+    t_end: f64,
+    rng: &mut R,
+    path: &mut Trajectory,
+) {
+    let mut x = path.x[0].clone();
+    let fresh_time = |t: f64, a: f64, rng: &mut R| {
+        if a > 0. {
+            t + rng.sample::<f64, _>(Exp1) / a
+        } else {
+            f64::INFINITY
+        }
+    };
+    let mut a = network.propensities(&x);
+    let mut times: Vec<f64> = a.iter().map(|a_j| fresh_time(0., *a_j, rng)).collect();
+    loop {
+        let (mu, t) = times
+            .iter()
+            .cloned()
+            .enumerate()
+            .min_by(|(_, s), (_, t)| s.total_cmp(t))
+            .unwrap();
+        if t > t_end {
+            break;
+        }
+        x += &network.reactions[mu].stoichiometry;
+        path.t.push(t);
+        path.x.push(x.clone());
+
+        // the closures give no dependency graph, so every propensity is recomputed
+        let a_next = network.propensities(&x);
+        for j in 0..times.len() {
+            times[j] = if j == mu || a[j] == 0. {
+                fresh_time(t, a_next[j], rng)
+            } else if a_next[j] == 0. {
+                f64::INFINITY
+            } else {
+                t + a[j] / a_next[j] * (times[j] - t)
+            };
+        }
+        a = a_next;
+    }
+}
+
+fn tau_leaping<R: Rng>(
+    network: &ReactionNetwork,
+    t_end: f64,
+    tau: f64,
+    rng: &mut R,
+    path: &mut Trajectory,
+) {
+    let mut t = 0.;
+    let mut x = path.x[0].clone();
+    while t < t_end {
+        let a = network.propensities(&x);
+        if a.iter().all(|a_j| *a_j == 0.) {
+            break;
+        }
+        let mut h = tau.min(t_end - t);
+        let x_next = loop {
+            let mut x_next = x.clone();
+            for (a_j, reaction) in a.iter().zip(&network.reactions) {
+                if *a_j > 0. {
+                    let firings: f64 = Poisson::new(a_j * h).unwrap().sample(rng);
+                    x_next.scaled_add(firings, &reaction.stoichiometry);
+                }
+            }
+            if x_next.iter().all(|x_i| *x_i >= 0.) {
+                break x_next;
+            }
+            h /= 2.;
+        };
+        t += h;
+        x = x_next;
+        path.t.push(t);
+        path.x.push(x.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn birth_death_mean_follows_the_rate_equation() {
+        // X -> 2X at rate b x and X -> 0 at rate d x, so the mean is x_0 e^((b - d) t)
+        let (b, d, x_0, t_end, n_paths) = (1., 0.5, 20., 2., 2000);
+        let network = ReactionNetwork::new(vec!["x"])
+            .reaction("birth", move |x| b * x[0], array![1.])
+            .reaction("death", move |x| d * x[0], array![-1.]);
+        let exact = x_0 * ((b - d) * t_end).exp();
+        for method in [
+            SSAMethod::Direct,
+            SSAMethod::NextReaction,
+            SSAMethod::TauLeaping(0.01),
+        ] {
+            let paths =
+                SSASolver::new(method, 7).trajectories(&network, &array![x_0], t_end, n_paths);
+            let mean = paths
+                .iter()
+                .map(|path| path.x.last().unwrap()[0])
+                .sum::<f64>()
+                / n_paths as f64;
+            // the standard error of the mean is about 0.4
+            assert!((mean - exact).abs() < 1.6, "{} against {}", mean, exact);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+pub mod analysis;
+pub mod chart;
+pub mod dual;
+pub mod fitting;
+pub mod gillespie;
+pub mod linalg;
+pub mod models;
+pub mod ode_solvers;
+pub mod sde_solvers;
\ No newline at end of file
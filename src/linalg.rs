@@ -0,0 +1,131 @@
+use ndarray::{Array1, Array2};
+
+/// LU factorisation with partial pivoting, kept so one matrix can be solved against many times.
+pub struct LuDecomposition {
+    lu: Array2<f64>,
+    pivots: Vec<usize>,
+}
+
+impl LuDecomposition {
+    pub fn new(a: &Array2<f64>) -> Option<Self> {
+        let n = a.nrows();
+        assert_eq!(n, a.ncols(), "LU decomposition needs a square matrix");
+        let mut lu = a.clone();
+        let mut pivots: Vec<usize> = (0..n).collect();
+
+        for k in 0..n {
+            let p = (k..n)
+                .max_by(|&i, &j| lu[[i, k]].abs().total_cmp(&lu[[j, k]].abs()))
+                .unwrap();
+            if lu[[p, k]] == 0. || !lu[[p, k]].is_finite() {
+                return None;
+            }
+            if p != k {
+                for j in 0..n {
+                    lu.swap([k, j], [p, j]);
+                }
+                pivots.swap(k, p);
+            }
+            for i in k + 1..n {
+                lu[[i, k]] /= lu[[k, k]];
+                let l_ik = lu[[i, k]];
+                for j in k + 1..n {
+                    lu[[i, j]] -= l_ik * lu[[k, j]];
+                }
+            }
+        }
+        Some(Self { lu, pivots })
+    }
+
+    pub fn solve(&self, b: &Array1<f64>) -> Array1<f64> {
+        let n = self.lu.nrows();
+        let mut x: Array1<f64> = self.pivots.iter().map(|&p| b[p]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] -= self.lu[[i, j]] * x[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] -= self.lu[[i, j]] * x[j];
+            }
+            x[i] /= self.lu[[i, i]];
+        }
+        x
+    }
+}
+
+pub fn solve(a: &Array2<f64>, b: &Array1<f64>) -> Option<Array1<f64>> {
+    LuDecomposition::new(a).map(|lu| lu.solve(b))
+}
+
+/// Eigenvalues `(re, im)` of a 2x2 matrix from its trace and determinant, larger real part first.
+pub fn eigenvalues2(a: &Array2<f64>) -> [(f64, f64); 2] {
+    assert_eq!(a.dim(), (2, 2), "eigenvalues2 needs a 2x2 matrix");
+    let trace = a[[0, 0]] + a[[1, 1]];
+    let det = a[[0, 0]] * a[[1, 1]] - a[[0, 1]] * a[[1, 0]];
+    let discriminant = trace * trace / 4. - det;
+    if discriminant >= 0. {
+        let root = discriminant.sqrt();
+        [(trace / 2. + root, 0.), (trace / 2. - root, 0.)]
+    } else {
+        let root = (-discriminant).sqrt();
+        [(trace / 2., root), (trace / 2., -root)]
+    }
+}
+
+/// Forward-difference Jacobian `J_ij = df_i / dx_j`.
+pub fn jacobian<F>(f: F, x: &Array1<f64>) -> Array2<f64>
+where
+    F: Fn(&Array1<f64>) -> Array1<f64>,
+{
+    let f_x = f(x);
+    let mut jac = Array2::zeros((f_x.len(), x.len()));
+    let mut x_h = x.clone();
+    for j in 0..x.len() {
+        let h = f64::EPSILON.sqrt() * x[j].abs().max(1.);
+        x_h[j] = x[j] + h;
+        let f_h = f(&x_h);
+        jac.column_mut(j).assign(&((&f_h - &f_x) / h));
+        x_h[j] = x[j];
+    }
+    jac
+}
+
+/// Modified Gram-Schmidt `a = q r` with orthonormal columns in `q` and `r` upper triangular
+/// with a non-negative diagonal.
+pub fn qr(a: &Array2<f64>) -> (Array2<f64>, Array2<f64>) {
+    let n = a.ncols();
+    let mut q = a.clone();
+    let mut r = Array2::zeros((n, n));
+    for j in 0..n {
+        for i in 0..j {
+            r[[i, j]] = q.column(i).dot(&q.column(j));
+            let q_i = q.column(i).to_owned();
+            q.column_mut(j).scaled_add(-r[[i, j]], &q_i);
+        }
+        r[[j, j]] = q.column(j).dot(&q.column(j)).sqrt();
+        if r[[j, j]] > 0. {
+            q.column_mut(j).mapv_inplace(|q_ij| q_ij / r[[j, j]]);
+        }
+    }
+    (q, r)
+}
+
+/// Lower-triangular `l` with `a = l l^T`, `None` unless `a` is symmetric positive definite.
+pub fn cholesky(a: &Array2<f64>) -> Option<Array2<f64>> {
+    let n = a.nrows();
+    let mut l = Array2::zeros((n, n));
+    for j in 0..n {
+        let d = a[[j, j]] - (0..j).map(|k| l[[j, k]] * l[[j, k]]).sum::<f64>();
+        if d <= 0. || !d.is_finite() {
+            return None;
+        }
+        l[[j, j]] = d.sqrt();
+        for i in j + 1..n {
+            l[[i, j]] =
+                (a[[i, j]] - (0..j).map(|k| l[[i, k]] * l[[j, k]]).sum::<f64>()) / l[[j, j]];
+        }
+    }
+    Some(l)
+}
\ No newline at end of file
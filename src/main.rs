@@ -1,30 +1,87 @@
-use chart::draw_bitmap_line_chart;
-use ode_solvers::{ODESolver1, ODESolver2, SolverMethod};
-
-mod chart;
-mod models;
-mod ode_solvers;
+use math_modelling::chart::{
+    draw_bitmap_band_chart, draw_bitmap_bar_chart, draw_bitmap_basin_map,
+    draw_bitmap_bifurcation_diagram, draw_bitmap_corner_plot, draw_bitmap_envelope_chart,
+    draw_bitmap_line_chart, draw_bitmap_phase_portrait, draw_bitmap_scatter_chart,
+    draw_bitmap_trace_plot, draw_svg_phase_portrait,
+};
+use math_modelling::gillespie::{SSAMethod, SSASolver};
+use math_modelling::models;
+use math_modelling::ode_solvers::{
+    ODESolver, ODESolver1, ODESolver2, PartitionedSolver, PatankarMethod, PatankarSolver,
+    SolverMethod, SymplecticMethod,
+};
+use math_modelling::sde_solvers::{Interpretation, SDEMethod, SDESolver};
 
 fn main() {
     let solver1 = ODESolver1::new(SolverMethod::Three8th);
     let solver2 = ODESolver2::new(SolverMethod::ForwardEuler);
+    let positive_solver1 = solver1.with_positivity();
+    let positive_solver2 = solver2.with_positivity();
+    let implicit_solver2 = ODESolver2::new(SolverMethod::BackwardEuler);
+    let adaptive_solver1 = ODESolver1::new(SolverMethod::DormandPrince54);
+    let multistep_solver1 = ODESolver1::new(SolverMethod::AdamsBashforth(4));
+    let solver = ODESolver::new(SolverMethod::DormandPrince54);
+    let patankar_solver = PatankarSolver::new(PatankarMethod::MPRK22);
+    let partitioned_solver = PartitionedSolver::new(SymplecticMethod::StormerVerlet);
+    let ssa_solver = SSASolver::new(SSAMethod::Direct, 42);
+    let sde_solver = SDESolver::new(SDEMethod::Milstein, Interpretation::Ito, 42);
 
     models::bacteria_growth(solver1, draw_bitmap_line_chart);
     models::gompertz(solver1, draw_bitmap_line_chart);
     models::demographic(solver1, draw_bitmap_line_chart);
     models::seasonal_capacity(solver1, draw_bitmap_line_chart);
-    models::constant_rate_harvesting(solver1, draw_bitmap_line_chart);
+    models::gompertz_adaptive(adaptive_solver1, draw_bitmap_line_chart);
+    models::demographic_adaptive(adaptive_solver1, draw_bitmap_line_chart);
+    models::seasonal_capacity_adaptive(adaptive_solver1, draw_bitmap_line_chart);
+    models::seasonal_capacity_multistep(multistep_solver1, draw_bitmap_line_chart);
+    models::constant_rate_harvesting(positive_solver1, draw_bitmap_line_chart);
+    models::constant_rate_harvesting_collapse(solver, draw_bitmap_line_chart);
     models::optimal_harvesting(solver1, draw_bitmap_line_chart);
     models::generalist_vs_specialist_predator(solver1, draw_bitmap_line_chart);
-    models::pest_control1(solver2, draw_bitmap_line_chart);
+    models::pest_control1(positive_solver2, draw_bitmap_line_chart);
     models::pest_control2(solver2, draw_bitmap_line_chart);
+    models::pest_control2_global_sensitivity(solver2, draw_bitmap_bar_chart);
     models::lotka_volterra(solver2, draw_bitmap_line_chart);
+    models::lotka_volterra_invariant(partitioned_solver, draw_bitmap_line_chart);
+    models::lotka_volterra_sensitivity(solver, draw_bitmap_line_chart);
+    models::lotka_volterra_fit(solver, draw_bitmap_line_chart);
     models::logistic_predator_prey(solver2, draw_bitmap_line_chart);
     models::competition_model(solver2, draw_bitmap_line_chart);
-    models::another_competition_model(solver2, draw_bitmap_line_chart);
+    models::another_competition_model(implicit_solver2, draw_bitmap_line_chart);
     models::mutualism1(solver2, draw_bitmap_line_chart);
     models::mutualism2(solver2, draw_bitmap_line_chart);
     models::rabies_pest1(solver2, draw_bitmap_line_chart);
-    models::rabies_pest2(solver2, draw_bitmap_line_chart);
+    models::rabies_pest2(positive_solver2, draw_bitmap_line_chart);
+    models::rabies_pest2_patankar(patankar_solver, draw_bitmap_line_chart);
     models::rabies_pest3(solver2, draw_bitmap_line_chart);
+    models::rabies_pest1_posterior(
+        solver,
+        draw_bitmap_trace_plot,
+        draw_bitmap_corner_plot,
+        draw_bitmap_band_chart,
+    );
+    models::hutchinson(solver, draw_bitmap_line_chart);
+    models::food_chain(solver, draw_bitmap_line_chart);
+    models::equilibrium_tables();
+    models::oscillation_reports(solver);
+    models::chaos_diagnostics(solver);
+    models::optimal_harvesting_bifurcation(solver, draw_bitmap_bifurcation_diagram);
+    models::constant_rate_harvesting_bifurcation(solver, draw_bitmap_bifurcation_diagram);
+    models::seasonal_capacity_orbit_diagram(solver, draw_bitmap_scatter_chart);
+    models::seasonal_capacity_stroboscopic_map(solver, draw_bitmap_scatter_chart);
+    models::food_chain_poincare_section(solver, draw_bitmap_scatter_chart);
+    models::phase_portraits(
+        solver2,
+        &[
+            (draw_bitmap_phase_portrait, "png"),
+            (draw_svg_phase_portrait, "svg"),
+        ],
+    );
+    models::competition_model_basins(solver2, draw_bitmap_basin_map);
+    models::another_competition_model_basins(solver2, draw_bitmap_basin_map);
+    models::stochastic_gompertz(sde_solver, draw_bitmap_band_chart);
+    models::stochastic_seasonal_capacity(sde_solver, draw_bitmap_band_chart);
+    models::stochastic_lotka_volterra(sde_solver, draw_bitmap_band_chart);
+    models::lotka_volterra_uncertainty(solver, draw_bitmap_envelope_chart);
+    models::stochastic_rabies_pest1(ssa_solver, draw_bitmap_band_chart);
 }
\ No newline at end of file
@@ -1,19 +1,93 @@
-use crate::models::ChartDrawer;
-use crate::ode_solvers::ODESolver2;
-use ndarray::Array1;
+use crate::analysis::basin_map;
+use crate::dual::Real;
+use crate::models::{BasinMapDrawer, ChartDrawer};
+use crate::ode_solvers::{ODESolver2, RightHandSide};
+use ndarray::{array, Array1};
 
+/// `n' = (alpha1 - gamma1 (beta1 n + beta2 p)) n`, `p' = (alpha2 - gamma2 (beta1 n + beta2 p)) p`,
+/// both species competing for the resource `beta1 n + beta2 p`.
+pub struct AnotherCompetitionModel {
+    pub alpha1: f64,
+    pub beta1: f64,
+    pub gamma1: f64,
+    pub alpha2: f64,
+    pub beta2: f64,
+    pub gamma2: f64,
+}
+
+impl Default for AnotherCompetitionModel {
+    fn default() -> Self {
+        Self {
+            alpha1: 15.,
+            beta1: 0.1,
+            gamma1: 500.,
+            alpha2: 10.0,
+            beta2: 0.01,
+            gamma2: 600.,
+        }
+    }
+}
+
+impl RightHandSide for AnotherCompetitionModel {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("alpha1", self.alpha1),
+            ("beta1", self.beta1),
+            ("gamma1", self.gamma1),
+            ("alpha2", self.alpha2),
+            ("beta2", self.beta2),
+            ("gamma2", self.gamma2),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (n, p) = (y[0], y[1]);
+        let (alpha1, beta1, gamma1, alpha2, beta2, gamma2) = (q[0], q[1], q[2], q[3], q[4], q[5]);
+        let resource = beta1 * n + beta2 * p;
+        array![
+            (alpha1 - gamma1 * resource) * n,
+            (alpha2 - gamma2 * resource) * p
+        ]
+    }
+}
+
+/// Starts far above the resource limit, where the rates are around `-1e5`, so explicit steps of
+/// `1e-4` diverge. Run it with an implicit method such as `BackwardEuler`.
 pub fn another_competition_model(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (alpha1, beta1, gamma1, alpha2, beta2, gamma2) = (15., 0.1, 500., 10.0, 0.01, 600.);
+    let model = AnotherCompetitionModel::default();
     let (n0, p0, tn, n_steps) = (2000., 100., 1., 10000);
-    let dn_by_dt = |_: f64, n: f64, p: f64| (alpha1 - gamma1 * (beta1 * n + beta2 * p)) * n;
-    let dp_by_dt = |_: f64, n: f64, p: f64| (alpha1 - gamma1 * (beta1 * n + beta2 * p)) * p;
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (nt, pt) = ode_solver.solve(dn_by_dt, dp_by_dt, tn / n_steps as f64, n_steps, &t, n0, p0);
+    let (nt, pt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, n0, p0);
 
     chart_drawer(
         "plots/another_competition_model.png",
         "Competition Model",
         vec![(t.clone(), nt, "n"), (t, pt, "p")],
     );
+}
+
+/// Basins of attraction of `another_competition_model`. Both species are limited by the same
+/// resource `beta1 n + beta2 p`, and `n` still grows at the level `alpha2 / gamma2` where `p`
+/// stops, so `n` excludes `p` from every start with `n` present. The separatrix is the `p` axis,
+/// the stable manifold of the saddle at `p = alpha2 / (gamma2 beta2)`.
+pub fn another_competition_model_basins(ode_solver: ODESolver2, chart_drawer: BasinMapDrawer) {
+    let model = AnotherCompetitionModel::default();
+    let (tn, n_steps, resolution) = (5., 5000, 60);
+
+    let basins = basin_map(
+        ode_solver,
+        &model,
+        [(0., 0.6), (0., 3.)],
+        ["n0", "p0"],
+        resolution,
+        tn,
+        n_steps,
+    );
+
+    chart_drawer(
+        "plots/another_competition_model_basins.png",
+        "Another Competition Model - Basins of Attraction",
+        &basins,
+    );
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+use super::{FoodChain, SeasonalCapacity};
+use crate::analysis::{
+    largest_lyapunov_exponent, lyapunov_spectrum, zero_one_test, LyapunovOptions,
+};
+use crate::ode_solvers::{ODESolver, RightHandSide};
+use ndarray::{array, s, Array1};
+
+/// Prints Lyapunov exponents and the 0-1 test for the forced `seasonal_capacity` model, which
+/// settles onto its periodic response, and for the chaotic `food_chain`.
+pub fn chaos_diagnostics(ode_solver: ODESolver) {
+    let seasonal_capacity = SeasonalCapacity::default();
+    let dn_by_dt = |t: f64, n: &Array1<f64>| seasonal_capacity.f(t, n);
+    let options = LyapunovOptions {
+        h: 0.01,
+        transient_steps: 10_000,
+        n_steps: 100_000,
+        renormalise_every: 10,
+    };
+    let n0 = array![2.];
+    println!(
+        "seasonal_capacity: largest Lyapunov exponent {}, 0-1 test K = {}",
+        largest_lyapunov_exponent(ode_solver, dn_by_dt, 0., &n0, &options),
+        zero_one_test(&sampled(ode_solver, dn_by_dt, &n0, &options, 200), 100, 42)
+    );
+
+    let model = FoodChain::default();
+    let dy_by_dt = |t: f64, y: &Array1<f64>| model.f(t, y);
+    let options = LyapunovOptions {
+        h: 0.05,
+        transient_steps: 20_000,
+        n_steps: 200_000,
+        renormalise_every: 10,
+    };
+    let y0 = array![0.8, 0.2, 8.];
+    println!(
+        "food_chain: Lyapunov spectrum {}, 0-1 test K = {}",
+        lyapunov_spectrum(ode_solver, dy_by_dt, 0., &y0, &options),
+        zero_one_test(&sampled(ode_solver, dy_by_dt, &y0, &options, 100), 100, 42)
+    );
+}
+
+/// 2000 samples of the first component, one every `every` steps after the transient.
+fn sampled<F>(
+    ode_solver: ODESolver,
+    f: F,
+    y_0: &Array1<f64>,
+    options: &LyapunovOptions,
+    every: usize,
+) -> Array1<f64>
+where
+    F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+{
+    let n_steps = options.transient_steps + 2000 * every;
+    let t = Array1::linspace(0., n_steps as f64 * options.h, n_steps + 1);
+    let y = ode_solver.solve(f, options.h, n_steps, &t, y_0);
+    y.slice(s![options.transient_steps + every..;every, 0])
+        .to_owned()
+}
\ No newline at end of file
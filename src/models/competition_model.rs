@@ -1,19 +1,94 @@
+use super::BasinMapDrawer;
+use crate::analysis::basin_map;
+use crate::dual::Real;
 use crate::models::ChartDrawer;
-use crate::ode_solvers::ODESolver2;
-use ndarray::Array1;
+use crate::ode_solvers::{ODESolver2, RightHandSide};
+use ndarray::{array, Array1};
+
+/// `n' = alpha1 n (1 - (n + beta1 p) / k1)`, `p' = alpha2 p (1 - (p + beta2 n) / k2)`.
+pub struct CompetitionModel {
+    pub alpha1: f64,
+    pub beta1: f64,
+    pub k1: f64,
+    pub alpha2: f64,
+    pub beta2: f64,
+    pub k2: f64,
+}
+
+impl Default for CompetitionModel {
+    fn default() -> Self {
+        Self {
+            alpha1: 15.,
+            beta1: 0.1,
+            k1: 500.,
+            alpha2: 10.0,
+            beta2: 0.01,
+            k2: 600.,
+        }
+    }
+}
+
+impl RightHandSide for CompetitionModel {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("alpha1", self.alpha1),
+            ("beta1", self.beta1),
+            ("k1", self.k1),
+            ("alpha2", self.alpha2),
+            ("beta2", self.beta2),
+            ("k2", self.k2),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (n, p) = (y[0], y[1]);
+        let (alpha1, beta1, k1, alpha2, beta2, k2) = (q[0], q[1], q[2], q[3], q[4], q[5]);
+        let one = T::from(1.);
+        array![
+            alpha1 * n * (one - (n + beta1 * p) / k1),
+            alpha2 * p * (one - (p + beta2 * n) / k2)
+        ]
+    }
+}
 
 pub fn competition_model(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (alpha1, beta1, k1, alpha2, beta2, k2) = (15., 0.1, 500., 10.0, 0.01, 600.);
+    let model = CompetitionModel::default();
     let (n0, p0, tn, n_steps) = (2000., 100., 1., 10000);
-    let dn_by_dt = |_: f64, n: f64, p: f64| alpha1 * n * (1. - (n + beta1 * p) / k1);
-    let dp_by_dt = |_: f64, n: f64, p: f64| alpha2 * p * (1. - (p + beta2 * n) / k2);
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (nt, pt) = ode_solver.solve(dn_by_dt, dp_by_dt, tn / n_steps as f64, n_steps, &t, n0, p0);
+    let (nt, pt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, n0, p0);
 
     chart_drawer(
         "plots/competition_model.png",
         "Competition Model",
         vec![(t.clone(), nt, "n"), (t, pt, "p")],
     );
+}
+
+/// Basins of attraction of `competition_model` with strong competition, `beta1 k2 > k1` and
+/// `beta2 k1 > k2`. The coexistence equilibrium is then a saddle, and whichever species starts
+/// on its side of the saddle's stable manifold excludes the other.
+pub fn competition_model_basins(ode_solver: ODESolver2, chart_drawer: BasinMapDrawer) {
+    let model = CompetitionModel {
+        beta1: 1.5,
+        beta2: 1.5,
+        ..CompetitionModel::default()
+    };
+    let (tn, n_steps, resolution) = (20., 10000, 80);
+
+    let basins = basin_map(
+        ode_solver,
+        &model,
+        [(0., 800.), (0., 800.)],
+        ["n0", "p0"],
+        resolution,
+        tn,
+        n_steps,
+    );
+
+    chart_drawer(
+        "plots/competition_model_basins.png",
+        "Competition Model - Basins of Attraction",
+        &basins,
+    );
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
-use super::ChartDrawer;
-use crate::ode_solvers::ODESolver1;
-use ndarray::Array1;
+use super::{BifurcationDiagramDrawer, ChartDrawer};
+use crate::analysis::{continue_equilibria, orbit_diagram, ContinuationOptions};
+use crate::ode_solvers::{Event, EventDirection, ODESolver, ODESolver1, RootFinder};
+use ndarray::{array, Array1};
 
 pub fn constant_rate_harvesting(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
     let (alpha, h, k) = (1., 2., 10.);
@@ -15,4 +16,82 @@ pub fn constant_rate_harvesting(ode_solver: ODESolver1, chart_drawer: ChartDrawe
         "Constant Rate Harvesting",
         vec![(t, nt, "population")],
     );
+}
+
+/// `constant_rate_harvesting` stopped when the population is harvested out. Starting below the
+/// lower equilibrium `n = k / 2 - sqrt(k^2 / 4 - h k / alpha)` it reaches zero in finite time,
+/// which a terminal event locates between steps.
+pub fn constant_rate_harvesting_collapse(ode_solver: ODESolver, chart_drawer: ChartDrawer) {
+    let (alpha, h, k) = (1., 2., 10.);
+    let (n0, tn, n_steps) = (2., 3., 300);
+    let dn_by_dt = |_: f64, n: &Array1<f64>| n.mapv(|n| alpha * n * (1. - n / k) - h);
+    let collapse = Event::new(|_, n| n[0], EventDirection::Falling, true);
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let (t, nt, records) = ode_solver.solve_with_events(
+        dn_by_dt,
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &array![n0],
+        &[collapse],
+        RootFinder::Illinois,
+    );
+    match records.first() {
+        Some(record) => println!(
+            "constant_rate_harvesting: the population collapses at t = {}",
+            record.t
+        ),
+        None => println!("constant_rate_harvesting: no collapse before t = {}", tn),
+    }
+
+    chart_drawer(
+        "plots/constant_rate_harvesting_collapse.png",
+        "Constant Rate Harvesting - Collapse",
+        vec![(t, nt.column(0).to_owned(), "population")],
+    );
+}
+
+/// Equilibria of `constant_rate_harvesting` against the harvest `h`, which meet and vanish in a
+/// saddle-node at `h = alpha k / 4`. The orbit diagram starts every run at the carrying capacity
+/// and keeps the population non-negative, so past the fold it shows the collapse to zero.
+pub fn constant_rate_harvesting_bifurcation(
+    ode_solver: ODESolver,
+    chart_drawer: BifurcationDiagramDrawer,
+) {
+    let (alpha, k) = (1., 10.);
+    let (h_min, h_max) = (0., 3.);
+    let dn_by_dt = |n: &Array1<f64>, h: f64| n.mapv(|n| alpha * n * (1. - n / k) - h);
+
+    let branch = continue_equilibria(
+        dn_by_dt,
+        &array![k],
+        h_min,
+        (h_min, h_max),
+        &ContinuationOptions::default(),
+    );
+    for b in &branch.bifurcations {
+        println!(
+            "constant_rate_harvesting: {} at h = {}, n = {}",
+            b.kind, b.parameter, b.y[0]
+        );
+    }
+
+    let orbits = orbit_diagram(
+        ode_solver.with_positivity(),
+        |_, n, h| dn_by_dt(n, h),
+        &array![k],
+        &Array1::linspace(h_min, h_max, 61),
+        0,
+        0.02,
+        50.,
+        10.,
+    );
+
+    chart_drawer(
+        "plots/constant_rate_harvesting_bifurcation.png",
+        "Constant Rate Harvesting - Equilibria against h",
+        &[branch],
+        vec![(orbits.maxima.0, orbits.maxima.1, "long-run population")],
+    );
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use super::ChartDrawer;
-use crate::ode_solvers::ODESolver1;
+use crate::ode_solvers::{AdaptiveOptions, ODESolver1};
 use ndarray::Array1;
 
 pub fn demographic(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
@@ -15,4 +15,25 @@ pub fn demographic(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
         "Demographic Model",
         vec![(t, nt, "population")],
     );
+}
+
+/// `demographic` with an embedded pair choosing the steps, in place of a million fixed ones.
+pub fn demographic_adaptive(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
+    let (alpha, a, k) = (0.8, 1.5, 10.);
+    let (n0, tn) = (2., 10.);
+    let dn_by_dt = |_: f64, n: f64| a * (n / alpha) * (1. - (n / k).powf(alpha));
+
+    let options = AdaptiveOptions {
+        h_max: tn / 500.,
+        ..AdaptiveOptions::default()
+    };
+    let (t, nt) = ode_solver
+        .solve_adaptive(dn_by_dt, (0., tn), n0, &options)
+        .expect("the population stays bounded over the span");
+
+    chart_drawer(
+        "plots/demographic_model_adaptive.png",
+        "Demographic Model - Adaptive Steps",
+        vec![(t, nt, "population")],
+    );
 }
\ No newline at end of file
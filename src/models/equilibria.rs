@@ -0,0 +1,74 @@
+use super::{
+    AnotherCompetitionModel, CompetitionModel, LogisticPredatorPrey, LotkaVolterra, Mutualism1,
+    Mutualism2, PestControl1, PestControl2, RabiesPest1, RabiesPest2, RabiesPest3,
+};
+use crate::analysis::{find_equilibria, print_equilibrium_table};
+
+/// Prints the equilibria of the two-species models with their stability. The pest control models
+/// only have the origin in the positive quadrant, where `N / (N + n)` leaves them singular.
+pub fn equilibrium_tables() {
+    let starts = 20;
+    let populations = [(0., 3000.), (0., 3000.)];
+    let resource_limited = [(0., 3.), (0., 3.)];
+    let epidemic = [(0., 500.), (0., 500.)];
+
+    print_equilibrium_table(
+        "lotka_volterra",
+        ["preys", "predator"],
+        &find_equilibria(&LotkaVolterra::default(), populations, starts),
+    );
+    print_equilibrium_table(
+        "logistic_predator_prey",
+        ["preys", "predator"],
+        &find_equilibria(&LogisticPredatorPrey::default(), populations, starts),
+    );
+    print_equilibrium_table(
+        "competition_model",
+        ["n", "p"],
+        &find_equilibria(&CompetitionModel::default(), populations, starts),
+    );
+    print_equilibrium_table(
+        "another_competition_model",
+        ["n", "p"],
+        &find_equilibria(
+            &AnotherCompetitionModel::default(),
+            resource_limited,
+            starts,
+        ),
+    );
+    print_equilibrium_table(
+        "mutualism1",
+        ["n", "p"],
+        &find_equilibria(&Mutualism1::default(), populations, starts),
+    );
+    print_equilibrium_table(
+        "mutualism2",
+        ["n", "p"],
+        &find_equilibria(&Mutualism2::default(), populations, starts),
+    );
+    print_equilibrium_table(
+        "rabies_pest1",
+        ["susceptible", "infective"],
+        &find_equilibria(&RabiesPest1::default(), epidemic, starts),
+    );
+    print_equilibrium_table(
+        "rabies_pest2",
+        ["susceptible", "infective"],
+        &find_equilibria(&RabiesPest2::default(), epidemic, starts),
+    );
+    print_equilibrium_table(
+        "rabies_pest3",
+        ["susceptible", "infective"],
+        &find_equilibria(&RabiesPest3::default(), epidemic, starts),
+    );
+    print_equilibrium_table(
+        "pest_control1",
+        ["pest", "insect"],
+        &find_equilibria(&PestControl1::default(), resource_limited, starts),
+    );
+    print_equilibrium_table(
+        "pest_control2",
+        ["pest", "insect"],
+        &find_equilibria(&PestControl2::default(), resource_limited, starts),
+    );
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use super::ChartDrawer;
+use crate::analysis::{poincare_section, Section};
+use crate::dual::Real;
+use crate::ode_solvers::{EventDirection, ODESolver, RightHandSide};
+use ndarray::{array, Array1};
+
+/// Hastings and Powell's three-species food chain: logistic prey `x`, a consumer `y` and a top
+/// predator `z`, each feeding with a saturating response `a u / (1 + b u)`. Chaotic at the
+/// default parameters.
+pub struct FoodChain {
+    pub a1: f64,
+    pub b1: f64,
+    pub a2: f64,
+    pub b2: f64,
+    pub d1: f64,
+    pub d2: f64,
+}
+
+impl Default for FoodChain {
+    fn default() -> Self {
+        Self {
+            a1: 5.,
+            b1: 3.,
+            a2: 0.1,
+            b2: 2.,
+            d1: 0.4,
+            d2: 0.01,
+        }
+    }
+}
+
+impl RightHandSide for FoodChain {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("a1", self.a1),
+            ("b1", self.b1),
+            ("a2", self.a2),
+            ("b2", self.b2),
+            ("d1", self.d1),
+            ("d2", self.d2),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (x, y, z) = (y[0], y[1], y[2]);
+        let (a1, b1, a2, b2, d1, d2) = (q[0], q[1], q[2], q[3], q[4], q[5]);
+        let one = T::from(1.);
+        let f1 = a1 * x / (one + b1 * x);
+        let f2 = a2 * y / (one + b2 * y);
+        array![
+            x * (one - x) - f1 * y,
+            f1 * y - f2 * z - d1 * y,
+            f2 * z - d2 * z
+        ]
+    }
+}
+
+pub fn food_chain(ode_solver: ODESolver, chart_drawer: ChartDrawer) {
+    let model = FoodChain::default();
+    let (x0, y0, z0, tn, n_steps) = (0.8, 0.2, 8., 1000., 20000);
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let y = ode_solver.solve_rhs(
+        &model,
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &array![x0, y0, z0],
+    );
+
+    chart_drawer(
+        "plots/food_chain.png",
+        "Three Species Food Chain",
+        vec![
+            (t.clone(), y.column(0).to_owned(), "prey"),
+            (t.clone(), y.column(1).to_owned(), "consumer"),
+            (t, y.column(2).to_owned(), "top predator"),
+        ],
+    );
+}
+
+/// Poincaré section of the chaotic `food_chain` where the consumer falls through `y = 0.25`,
+/// plotted as the top predator against the prey, and the first-return map of the top predator.
+pub fn food_chain_poincare_section(ode_solver: ODESolver, chart_drawer: ChartDrawer) {
+    let model = FoodChain::default();
+    let (h, n_steps, transient) = (0.05, 400_000, 1000.);
+    let section = Section::Hyperplane {
+        normal: array![0., 1., 0.],
+        offset: 0.25,
+        direction: EventDirection::Falling,
+    };
+
+    let crossings = poincare_section(
+        ode_solver,
+        |t, y| model.f(t, y),
+        &section,
+        h,
+        n_steps,
+        0.,
+        &array![0.8, 0.2, 8.],
+        transient,
+    );
+
+    chart_drawer(
+        "plots/food_chain_poincare_section.png",
+        "Three Species Food Chain - Poincaré Section y = 0.25",
+        vec![(
+            crossings.y.column(0).to_owned(),
+            crossings.y.column(2).to_owned(),
+            "(prey, top predator)",
+        )],
+    );
+    let (z_k, z_k1) = crossings.return_map(2);
+    chart_drawer(
+        "plots/food_chain_return_map.png",
+        "Three Species Food Chain - Return Map of the Top Predator",
+        vec![(z_k, z_k1, "(z_k, z_k+1)")],
+    );
+}
\ No newline at end of file
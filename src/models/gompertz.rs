@@ -1,6 +1,7 @@
-use super::ChartDrawer;
-use crate::ode_solvers::ODESolver1;
-use ndarray::Array1;
+use super::{BandChartDrawer, ChartDrawer};
+use crate::ode_solvers::{AdaptiveOptions, ODESolver1};
+use crate::sde_solvers::SDESolver;
+use ndarray::{array, Array1};
 
 pub fn gompertz(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
     let (alpha, k) = (0.8, 1.);
@@ -15,4 +16,57 @@ pub fn gompertz(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
         "Gompertz Model",
         vec![(t, nt, "population")],
     );
+}
+
+/// `gompertz` with an embedded pair choosing the steps, in place of a million fixed ones.
+pub fn gompertz_adaptive(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
+    let (alpha, k) = (0.8, 1.);
+    let (n0, tn) = (2., 2.);
+    let dn_by_dt = |_: f64, n: f64| -alpha * n * f64::ln(n / k);
+
+    let options = AdaptiveOptions {
+        h_max: tn / 500.,
+        ..AdaptiveOptions::default()
+    };
+    let (t, nt) = ode_solver
+        .solve_adaptive(dn_by_dt, (0., tn), n0, &options)
+        .expect("the population stays bounded over the span");
+
+    chart_drawer(
+        "plots/gompertz_model_adaptive.png",
+        "Gompertz Model - Adaptive Steps",
+        vec![(t, nt, "population")],
+    );
+}
+
+/// Gompertz growth with multiplicative environmental noise, `dN = -alpha N ln(N / k) dt + sigma N dW`.
+pub fn stochastic_gompertz(sde_solver: SDESolver, chart_drawer: BandChartDrawer) {
+    let (alpha, k, sigma) = (0.8, 1., 0.2);
+    let (n0, tn, n_steps, n_paths) = (2., 2., 1000, 200);
+    let drift = |_: f64, n: &Array1<f64>| n.mapv(|n| -alpha * n * f64::ln(n / k));
+    let diffusion = |_: f64, n: &Array1<f64>| sigma * n;
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let stats = sde_solver.ensemble(
+        drift,
+        diffusion,
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &array![n0],
+        n_paths,
+        &[0.05, 0.95],
+    );
+
+    chart_drawer(
+        "plots/stochastic_gompertz_model.png",
+        "Gompertz Model with Multiplicative Noise",
+        vec![(
+            t.clone(),
+            stats.quantile(0.05).column(0).to_owned(),
+            stats.quantile(0.95).column(0).to_owned(),
+            "90% band",
+        )],
+        vec![(t, stats.mean.column(0).to_owned(), "mean population")],
+    );
 }
\ No newline at end of file
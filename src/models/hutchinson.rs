@@ -0,0 +1,25 @@
+use super::ChartDrawer;
+use crate::ode_solvers::ODESolver;
+use ndarray::{array, Array1};
+
+pub fn hutchinson(ode_solver: ODESolver, chart_drawer: ChartDrawer) {
+    let (r, k, tau) = (1.8, 10., 1.);
+    let (n0, tn, n_steps) = (2., 30., 3000);
+    let dn_by_dt = |_: f64, n: &Array1<f64>, lagged: &[Array1<f64>]| r * n * (1. - &lagged[0] / k);
+
+    let solution = ode_solver.solve_dde(
+        dn_by_dt,
+        |_| array![n0],
+        &[tau],
+        (0., tn),
+        tn / n_steps as f64,
+    );
+    let t = Array1::linspace(0., tn, n_steps);
+    let nt = solution.resample(&t).column(0).to_owned();
+
+    chart_drawer(
+        "plots/hutchinson_model.png",
+        "Hutchinson Delayed Logistic",
+        vec![(t, nt, "population")],
+    );
+}
\ No newline at end of file
@@ -1,15 +1,88 @@
-use super::ChartDrawer;
-use crate::ode_solvers::ODESolver2;
-use ndarray::Array1;
+use super::{BarChartDrawer, ChartDrawer};
+use crate::analysis::{morris_screening, sobol_indices, MorrisOptions, SobolOptions};
+use crate::dual::Real;
+use crate::ode_solvers::{ODESolver2, RightHandSide};
+use ndarray::{array, Array1};
 
+/// `N' = (a N / (N + n) - b) N - k N (N + n)` for the pest `N`, with the sterile insects `n`
+/// released once and dying off, `n' = -b n`.
+pub struct PestControl1 {
+    pub a: f64,
+    pub b: f64,
+    pub k: f64,
+}
+
+impl Default for PestControl1 {
+    fn default() -> Self {
+        Self {
+            a: 1.,
+            b: 2.,
+            k: 10.,
+        }
+    }
+}
+
+impl RightHandSide for PestControl1 {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![("a", self.a), ("b", self.b), ("k", self.k)]
+    }
+
+    #[allow(non_snake_case)]
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (N, n) = (y[0], y[1]);
+        let (a, b, k) = (q[0], q[1], q[2]);
+        array![(a * N / (N + n) - b) * N - k * N * (N + n), -b * n]
+    }
+}
+
+/// `pest_control1` with the sterile insects released in proportion to the pest,
+/// `n' = gamma N - b n`.
+pub struct PestControl2 {
+    pub a: f64,
+    pub b: f64,
+    pub gamma: f64,
+    pub k: f64,
+}
+
+impl Default for PestControl2 {
+    fn default() -> Self {
+        Self {
+            a: 1.,
+            b: 2.,
+            gamma: 3.,
+            k: 10.,
+        }
+    }
+}
+
+impl RightHandSide for PestControl2 {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("a", self.a),
+            ("b", self.b),
+            ("gamma", self.gamma),
+            ("k", self.k),
+        ]
+    }
+
+    #[allow(non_snake_case)]
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (N, n) = (y[0], y[1]);
+        let (a, b, gamma, k) = (q[0], q[1], q[2], q[3]);
+        array![
+            (a * N / (N + n) - b) * N - k * N * (N + n),
+            gamma * N - b * n
+        ]
+    }
+}
+
+#[allow(non_snake_case)]
 pub fn pest_control1(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (a, b, k) = (1., 2., 10.);
+    let model = PestControl1::default();
     let (N0, n0, tn, n_steps) = (2., 0.2, 1.6, 10000);
-    let dN_by_dt = |_: f64, N: f64, n: f64| (a * N / (N + n) - b) * N - k * N * (N + n);
-    let dn_by_dt = |_: f64, _: f64, n: f64| -b * n;
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (Nt, nt) = ode_solver.solve(dN_by_dt, dn_by_dt, tn / n_steps as f64, n_steps, &t, N0, n0);
+    let (Nt, nt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, N0, n0);
 
     chart_drawer(
         "plots/insect_pest_control.png",
@@ -18,18 +91,74 @@ pub fn pest_control1(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
     );
 }
 
+#[allow(non_snake_case)]
 pub fn pest_control2(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (a, b, gamma, k) = (1., 2., 3., 10.);
+    let model = PestControl2::default();
     let (N0, n0, tn, n_steps) = (2., 0.2, 1.6, 10000);
-    let dN_by_dt = |_: f64, N: f64, n: f64| (a * N / (N + n) - b) * N - k * N * (N + n);
-    let dn_by_dt = |_: f64, N: f64, n: f64| gamma * N - b * n;
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (Nt, nt) = ode_solver.solve(dN_by_dt, dn_by_dt, tn / n_steps as f64, n_steps, &t, N0, n0);
+    let (Nt, nt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, N0, n0);
 
     chart_drawer(
         "plots/insect_pest_control.png",
         "Insect Pest Control",
         vec![(t.clone(), Nt, "pest"), (t, nt, "insect")],
     );
+}
+
+/// Sobol indices and Morris screening of `pest_control2` with a, b, gamma and k each varied by
+/// half its value either way, for the pest left at the final time and the size and timing of the
+/// insect peak.
+#[allow(non_snake_case)]
+pub fn pest_control2_global_sensitivity(ode_solver: ODESolver2, bar_drawer: BarChartDrawer) {
+    let (N0, n0, tn, n_steps) = (2., 0.2, 1.6, 1600);
+    let parameters = [
+        ("a", (0.5, 1.5)),
+        ("b", (1., 3.)),
+        ("gamma", (1.5, 4.5)),
+        ("k", (5., 15.)),
+    ];
+    let outputs = [
+        ("pest at tn", "pest_at_tn"),
+        ("insect peak", "insect_peak"),
+        ("insect peak time", "insect_peak_time"),
+    ];
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let model = |p: &Array1<f64>| {
+        let model = PestControl2 {
+            a: p[0],
+            b: p[1],
+            gamma: p[2],
+            k: p[3],
+        };
+        let (Nt, nt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, N0, n0);
+        let peak = (0..t.len())
+            .max_by(|i, j| nt[*i].total_cmp(&nt[*j]))
+            .unwrap();
+        array![Nt[n_steps], nt[peak], t[peak]]
+    };
+
+    let names: Vec<&str> = parameters.iter().map(|(name, _)| *name).collect();
+    let sobol = sobol_indices(model, &parameters, &SobolOptions::default());
+    let morris = morris_screening(model, &parameters, &MorrisOptions::default());
+    for ((output, file), (s, m)) in outputs.iter().zip(sobol.iter().zip(&morris)) {
+        s.print_summary(&format!("Sobol indices of the {}", output));
+        m.print_summary(&format!("Morris screening of the {}", output));
+        bar_drawer(
+            &format!("plots/insect_pest_control_sobol_{}.png", file),
+            &format!("Insect Pest Control - Sobol Indices, {}", output),
+            &names,
+            vec![
+                (s.first_order.clone(), "first order"),
+                (s.total_order.clone(), "total order"),
+            ],
+        );
+        bar_drawer(
+            &format!("plots/insect_pest_control_morris_{}.png", file),
+            &format!("Insect Pest Control - Morris Screening, {}", output),
+            &names,
+            vec![(m.mu_star.clone(), "mu*"), (m.sigma.clone(), "sigma")],
+        );
+    }
 }
\ No newline at end of file
@@ -1,15 +1,57 @@
+use crate::dual::Real;
 use crate::models::ChartDrawer;
-use crate::ode_solvers::ODESolver2;
-use ndarray::Array1;
+use crate::ode_solvers::{ODESolver2, RightHandSide};
+use ndarray::{array, Array1};
+
+/// `n' = alpha1 n (1 - n / k - beta p)`, `p' = -alpha2 p (1 - gamma n)`.
+pub struct LogisticPredatorPrey {
+    pub alpha1: f64,
+    pub beta: f64,
+    pub alpha2: f64,
+    pub gamma: f64,
+    pub k: f64,
+}
+
+impl Default for LogisticPredatorPrey {
+    fn default() -> Self {
+        Self {
+            alpha1: 15.,
+            beta: 0.1,
+            alpha2: 10.0,
+            gamma: 0.01,
+            k: 200.,
+        }
+    }
+}
+
+impl RightHandSide for LogisticPredatorPrey {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("alpha1", self.alpha1),
+            ("beta", self.beta),
+            ("alpha2", self.alpha2),
+            ("gamma", self.gamma),
+            ("k", self.k),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (n, p) = (y[0], y[1]);
+        let (alpha1, beta, alpha2, gamma, k) = (q[0], q[1], q[2], q[3], q[4]);
+        let one = T::from(1.);
+        array![
+            alpha1 * n * (one - n / k - beta * p),
+            -alpha2 * p * (one - gamma * n)
+        ]
+    }
+}
 
 pub fn logistic_predator_prey(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (alpha1, beta, alpha2, gamma, k) = (15., 0.1, 10.0, 0.01, 200.);
+    let model = LogisticPredatorPrey::default();
     let (n0, p0, tn, n_steps) = (2000., 100., 1., 10000);
-    let dn_by_dt = |_: f64, n: f64, p: f64| alpha1 * n * (1. - n / k - beta * p);
-    let dp_by_dt = |_: f64, n: f64, p: f64| -alpha2 * p * (1. - gamma * n);
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (nt, pt) = ode_solver.solve(dn_by_dt, dp_by_dt, tn / n_steps as f64, n_steps, &t, n0, p0);
+    let (nt, pt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, n0, p0);
 
     chart_drawer(
         "plots/logistic_predator_prey.png",
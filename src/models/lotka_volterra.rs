@@ -1,19 +1,318 @@
-use super::ChartDrawer;
-use crate::ode_solvers::ODESolver2;
-use ndarray::Array1;
+use super::{BandChartDrawer, ChartDrawer, EnvelopeChartDrawer};
+use crate::analysis::{InputDistribution, MonteCarloOptions, UncertaintyPropagation};
+use crate::dual::Real;
+use crate::fitting::{FitProblem, LevenbergMarquardtOptions, NelderMeadOptions, Observations};
+use crate::ode_solvers::{
+    first_integral_drift, lotka_volterra_first_integral, ODESolver, ODESolver2, PartitionedSolver,
+    RightHandSide, SolverMethod,
+};
+use crate::sde_solvers::SDESolver;
+use ndarray::{array, stack, Array1, Axis};
+
+/// `n' = alpha1 n - beta1 n p`, `p' = -alpha2 p + beta2 n p`.
+pub struct LotkaVolterra {
+    pub alpha1: f64,
+    pub beta1: f64,
+    pub alpha2: f64,
+    pub beta2: f64,
+}
+
+impl Default for LotkaVolterra {
+    fn default() -> Self {
+        Self {
+            alpha1: 15.,
+            beta1: 0.1,
+            alpha2: 10.0,
+            beta2: 0.01,
+        }
+    }
+}
+
+impl LotkaVolterra {
+    pub fn tuple(&self) -> (f64, f64, f64, f64) {
+        (self.alpha1, self.beta1, self.alpha2, self.beta2)
+    }
+}
+
+impl RightHandSide for LotkaVolterra {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("alpha1", self.alpha1),
+            ("beta1", self.beta1),
+            ("alpha2", self.alpha2),
+            ("beta2", self.beta2),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (n, p) = (y[0], y[1]);
+        let (alpha1, beta1, alpha2, beta2) = (q[0], q[1], q[2], q[3]);
+        array![alpha1 * n - beta1 * n * p, -alpha2 * p + beta2 * n * p]
+    }
+}
 
 pub fn lotka_volterra(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (alpha1, beta1, alpha2, beta2) = (15., 0.1, 10.0, 0.01);
+    let model = LotkaVolterra::default();
     let (n0, p0, tn, n_steps) = (2000., 100., 1., 10000);
-    let dn_by_dt = |_: f64, n: f64, p: f64| alpha1 * n - beta1 * n * p;
-    let dp_by_dt = |_: f64, n: f64, p: f64| -alpha2 * p + beta2 * n * p;
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (nt, pt) = ode_solver.solve(dn_by_dt, dp_by_dt, tn / n_steps as f64, n_steps, &t, n0, p0);
+    let (nt, pt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, n0, p0);
 
     chart_drawer(
         "plots/lotka_volterra_model.png",
         "Predator Prey - Lotka Volterra",
         vec![(t.clone(), nt, "preys"), (t, pt, "predator")],
     );
+}
+
+/// Lotka-Volterra with independent multiplicative noise on the prey and predator growth rates.
+pub fn stochastic_lotka_volterra(sde_solver: SDESolver, chart_drawer: BandChartDrawer) {
+    let model = LotkaVolterra::default();
+    let (sigma1, sigma2) = (0.5, 0.5);
+    let (n0, p0, tn, n_steps, n_paths) = (2000., 100., 1., 10000, 100);
+    let drift = |t: f64, y: &Array1<f64>| model.f(t, y);
+    let diffusion = |_: f64, y: &Array1<f64>| array![sigma1 * y[0], sigma2 * y[1]];
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let stats = sde_solver.ensemble(
+        drift,
+        diffusion,
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &array![n0, p0],
+        n_paths,
+        &[0.05, 0.95],
+    );
+
+    let band = |j: usize, label| {
+        (
+            t.clone(),
+            stats.quantile(0.05).column(j).to_owned(),
+            stats.quantile(0.95).column(j).to_owned(),
+            label,
+        )
+    };
+    chart_drawer(
+        "plots/stochastic_lotka_volterra_model.png",
+        "Predator Prey - Lotka Volterra with Multiplicative Noise",
+        vec![band(0, "preys 90% band"), band(1, "predator 90% band")],
+        vec![
+            (t.clone(), stats.mean.column(0).to_owned(), "mean preys"),
+            (t.clone(), stats.mean.column(1).to_owned(), "mean predator"),
+        ],
+    );
+}
+
+/// Lotka-Volterra with uncertain rates and starting prey population, propagated by Monte Carlo.
+/// Unlike the noise in `stochastic_lotka_volterra`, each run is deterministic, and the envelopes
+/// widen as runs with different periods drift out of phase.
+pub fn lotka_volterra_uncertainty(ode_solver: ODESolver, envelope_drawer: EnvelopeChartDrawer) {
+    let model = LotkaVolterra::default();
+    let (n0, p0, tn, n_steps) = (2000., 100., 1., 2000);
+
+    let propagation = UncertaintyPropagation::new(ode_solver, &model, &array![n0, p0])
+        .uncertain(
+            "alpha1",
+            InputDistribution::Normal { mean: 15., sd: 1.5 }.truncated(10., 20.),
+        )
+        .uncertain(
+            "beta1",
+            InputDistribution::LogNormal {
+                mu: 0.1_f64.ln(),
+                sigma: 0.1,
+            },
+        )
+        .uncertain(
+            "alpha2",
+            InputDistribution::Uniform {
+                lower: 9.,
+                upper: 11.,
+            },
+        )
+        .uncertain(
+            "beta2",
+            InputDistribution::LogNormal {
+                mu: 0.01_f64.ln(),
+                sigma: 0.1,
+            },
+        )
+        .uncertain_initial_condition(
+            "n0",
+            0,
+            InputDistribution::Normal { mean: n0, sd: 200. }.truncated(0., f64::INFINITY),
+        );
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let stats = propagation.propagate(
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &MonteCarloOptions::default(),
+    );
+
+    envelope_drawer(
+        "plots/lotka_volterra_uncertainty.png",
+        "Predator Prey - Lotka Volterra with Uncertain Parameters",
+        &t,
+        &stats,
+        &["preys", "predator"],
+    );
+}
+
+/// Drift of the conserved quantity of `lotka_volterra` under forward Euler, implicit midpoint and
+/// the Poisson integrator in log coordinates.
+pub fn lotka_volterra_invariant(partitioned_solver: PartitionedSolver, chart_drawer: ChartDrawer) {
+    let model = LotkaVolterra::default();
+    let params = model.tuple();
+    let (n0, p0, tn, n_steps) = (2000., 100., 1., 10000);
+    let dy_by_dt = |t: f64, y: &Array1<f64>| model.f(t, y);
+    let first_integral = |y: &Array1<f64>| lotka_volterra_first_integral(params, y[0], y[1]);
+
+    let h = tn / n_steps as f64;
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let y0 = array![n0, p0];
+    let euler = ODESolver::new(SolverMethod::ForwardEuler).solve(dy_by_dt, h, n_steps, &t, &y0);
+    let midpoint =
+        ODESolver::new(SolverMethod::ImplicitMidpoint).solve(dy_by_dt, h, n_steps, &t, &y0);
+    let (nt, pt) = partitioned_solver.solve_lotka_volterra(params, h, n_steps, &t, n0, p0);
+    let poisson = stack(Axis(1), &[nt.view(), pt.view()]).unwrap();
+
+    let lines = vec![
+        (
+            t.clone(),
+            first_integral_drift(first_integral, &euler),
+            "forward Euler",
+        ),
+        (
+            t.clone(),
+            first_integral_drift(first_integral, &midpoint),
+            "implicit midpoint",
+        ),
+        (
+            t.clone(),
+            first_integral_drift(first_integral, &poisson),
+            "Poisson integrator",
+        ),
+    ];
+    for (_, drift, label) in &lines {
+        println!(
+            "{}: max first integral drift {:e}",
+            label,
+            drift.fold(0., |a: f64, d| a.max(*d))
+        );
+    }
+
+    chart_drawer(
+        "plots/lotka_volterra_first_integral_drift.png",
+        "Lotka Volterra - Relative Drift of the First Integral",
+        lines,
+    );
+}
+
+/// Derivatives of the `lotka_volterra` trajectory with respect to `beta2`, and how far the
+/// predator peak moves per unit change of it.
+pub fn lotka_volterra_sensitivity(ode_solver: ODESolver, chart_drawer: ChartDrawer) {
+    let (n0, p0, tn, n_steps) = (2000., 100., 1., 10000);
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let solution = ode_solver.solve_rhs_sensitivities(
+        &LotkaVolterra::default(),
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &array![n0, p0],
+    );
+    let dy_by_dbeta2 = solution.with_respect_to("beta2");
+
+    // the peak is a maximum, so it moves with dP/dbeta2 at the peak time
+    let peak = (0..t.len())
+        .max_by(|i, j| solution.y[[*i, 1]].total_cmp(&solution.y[[*j, 1]]))
+        .unwrap();
+    println!(
+        "predator peak {} at t = {}, d(peak)/d(beta2) = {}",
+        solution.y[[peak, 1]],
+        t[peak],
+        dy_by_dbeta2[[peak, 1]]
+    );
+
+    chart_drawer(
+        "plots/lotka_volterra_sensitivity.png",
+        "Predator Prey - Sensitivity to beta2",
+        vec![
+            (
+                t.clone(),
+                dy_by_dbeta2.column(0).to_owned(),
+                "d preys / d beta2",
+            ),
+            (t, dy_by_dbeta2.column(1).to_owned(), "d predator / d beta2"),
+        ],
+    );
+}
+
+/// Fits `lotka_volterra`, rates and starting populations, to the Hudson's Bay hare and lynx pelt
+/// records by Levenberg-Marquardt and by Nelder-Mead, and plots the first fit against the data.
+pub fn lotka_volterra_fit(ode_solver: ODESolver, chart_drawer: ChartDrawer) {
+    let observations = Observations::from_csv("data/hudson_bay_hare_lynx.csv");
+    let guess = LotkaVolterra {
+        alpha1: 0.5,
+        beta1: 0.02,
+        alpha2: 0.9,
+        beta2: 0.02,
+    };
+    let problem = FitProblem::new(
+        ode_solver,
+        &guess,
+        &observations,
+        &observations.y.row(0).to_owned(),
+        0.05,
+    )
+    .observe("hare", 0)
+    .observe("lynx", 1)
+    .free("alpha1", (0.01, 5.))
+    .free("beta1", (1e-4, 1.))
+    .free("alpha2", (0.01, 5.))
+    .free("beta2", (1e-4, 1.))
+    .free_initial_condition("hare0", 0, (1., 100.))
+    .free_initial_condition("lynx0", 1, (0.1, 100.));
+
+    let fit = problem.levenberg_marquardt(&LevenbergMarquardtOptions::default());
+    fit.print_report("lotka_volterra fitted to hare and lynx by Levenberg-Marquardt");
+    problem
+        .nelder_mead(&NelderMeadOptions::default())
+        .print_report("lotka_volterra fitted to hare and lynx by Nelder-Mead");
+
+    let e = &fit.estimates;
+    let model = LotkaVolterra {
+        alpha1: e[0],
+        beta1: e[1],
+        alpha2: e[2],
+        beta2: e[3],
+    };
+    // the line chart's axes start at zero, so count years from the first record
+    let t_obs = &observations.t - observations.t[0];
+    let (tn, n_steps) = (t_obs[t_obs.len() - 1], 2000);
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let y = ode_solver.solve_rhs(
+        &model,
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &array![e[4], e[5]],
+    );
+
+    chart_drawer(
+        "plots/lotka_volterra_fit.png",
+        "Lotka Volterra fitted to Hare and Lynx Pelts from 1900",
+        vec![
+            (t.clone(), y.column(0).to_owned(), "fitted hare"),
+            (t, y.column(1).to_owned(), "fitted lynx"),
+            (
+                t_obs.clone(),
+                observations.y.column(0).to_owned(),
+                "observed hare",
+            ),
+            (t_obs, observations.y.column(1).to_owned(), "observed lynx"),
+        ],
+    );
 }
\ No newline at end of file
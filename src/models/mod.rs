@@ -1,33 +1,73 @@
 mod another_competition_model;
 mod bacteria_growth;
+mod chaos;
 mod competition_model;
 mod constant_rate_harvesting;
 mod demographic;
+mod equilibria;
+mod food_chain;
 mod generalist_vs_specialist;
 mod gompertz;
+mod hutchinson;
 mod insect_pest_control;
 mod logistic_predator_prey;
 mod lotka_volterra;
 mod mutualism;
 mod optimal_harvesting;
+mod oscillations;
+mod phase_portraits;
 mod rabies_pest;
 mod seasonal_capacity;
 
-pub use another_competition_model::another_competition_model;
+pub use another_competition_model::{
+    another_competition_model, another_competition_model_basins, AnotherCompetitionModel,
+};
 pub use bacteria_growth::bacteria_growth;
-pub use competition_model::competition_model;
-pub use constant_rate_harvesting::constant_rate_harvesting;
-pub use demographic::demographic;
+pub use chaos::chaos_diagnostics;
+pub use competition_model::{competition_model, competition_model_basins, CompetitionModel};
+pub use constant_rate_harvesting::{
+    constant_rate_harvesting, constant_rate_harvesting_bifurcation,
+    constant_rate_harvesting_collapse,
+};
+pub use demographic::{demographic, demographic_adaptive};
+pub use equilibria::equilibrium_tables;
+pub use food_chain::{food_chain, food_chain_poincare_section, FoodChain};
 pub use generalist_vs_specialist::generalist_vs_specialist_predator;
-pub use gompertz::gompertz;
-pub use insect_pest_control::{pest_control1, pest_control2};
-pub use logistic_predator_prey::logistic_predator_prey;
-pub use lotka_volterra::lotka_volterra;
-pub use mutualism::{mutualism1, mutualism2};
-pub use optimal_harvesting::optimal_harvesting;
-pub use rabies_pest::{rabies_pest1, rabies_pest2, rabies_pest3};
-pub use seasonal_capacity::seasonal_capacity;
+pub use gompertz::{gompertz, gompertz_adaptive, stochastic_gompertz};
+pub use hutchinson::hutchinson;
+pub use insect_pest_control::{
+    pest_control1, pest_control2, pest_control2_global_sensitivity, PestControl1, PestControl2,
+};
+pub use logistic_predator_prey::{logistic_predator_prey, LogisticPredatorPrey};
+pub use lotka_volterra::{
+    lotka_volterra, lotka_volterra_fit, lotka_volterra_invariant, lotka_volterra_sensitivity,
+    lotka_volterra_uncertainty, stochastic_lotka_volterra, LotkaVolterra,
+};
+pub use mutualism::{mutualism1, mutualism2, Mutualism1, Mutualism2};
+pub use optimal_harvesting::{optimal_harvesting, optimal_harvesting_bifurcation};
+pub use oscillations::oscillation_reports;
+pub use phase_portraits::phase_portraits;
+pub use rabies_pest::{
+    rabies_pest1, rabies_pest1_posterior, rabies_pest2, rabies_pest2_patankar, rabies_pest3,
+    stochastic_rabies_pest1, RabiesPest1, RabiesPest2, RabiesPest3,
+};
+pub use seasonal_capacity::{
+    seasonal_capacity, seasonal_capacity_adaptive, seasonal_capacity_multistep,
+    seasonal_capacity_orbit_diagram, seasonal_capacity_stroboscopic_map,
+    stochastic_seasonal_capacity, SeasonalCapacity,
+};
 
+use crate::analysis::{BasinMap, Branch, PhasePortrait};
+use crate::chart::Band;
+use crate::fitting::Chain;
+use crate::sde_solvers::EnsembleStatistics;
 use ndarray::Array1;
 
-type ChartDrawer = fn(&str, &str, Vec<(Array1<f64>, Array1<f64>, &str)>);
\ No newline at end of file
+type ChartDrawer = fn(&str, &str, Vec<(Array1<f64>, Array1<f64>, &str)>);
+type BifurcationDiagramDrawer = fn(&str, &str, &[Branch], Vec<(Array1<f64>, Array1<f64>, &str)>);
+type BandChartDrawer = fn(&str, &str, Vec<Band>, Vec<(Array1<f64>, Array1<f64>, &str)>);
+type PhasePortraitDrawer = fn(&str, &str, &PhasePortrait);
+type BasinMapDrawer = fn(&str, &str, &BasinMap);
+type BarChartDrawer = fn(&str, &str, &[&str], Vec<(Array1<f64>, &str)>);
+type ChainDrawer = fn(&str, &str, &Chain);
+type EnvelopeChartDrawer = fn(&str, &str, &Array1<f64>, &EnsembleStatistics, &[&str]);
\ No newline at end of file
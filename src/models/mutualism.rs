@@ -1,15 +1,102 @@
+use crate::dual::Real;
 use crate::models::ChartDrawer;
-use crate::ode_solvers::ODESolver2;
-use ndarray::Array1;
+use crate::ode_solvers::{ODESolver2, RightHandSide};
+use ndarray::{array, Array1};
 
+/// `n' = alpha1 n (1 + beta1 p)`, `p' = alpha2 p (1 + beta2 n)`.
+pub struct Mutualism1 {
+    pub alpha1: f64,
+    pub beta1: f64,
+    pub alpha2: f64,
+    pub beta2: f64,
+}
+
+impl Default for Mutualism1 {
+    fn default() -> Self {
+        Self {
+            alpha1: 15.,
+            beta1: 0.1,
+            alpha2: 10.0,
+            beta2: 0.01,
+        }
+    }
+}
+
+impl RightHandSide for Mutualism1 {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("alpha1", self.alpha1),
+            ("beta1", self.beta1),
+            ("alpha2", self.alpha2),
+            ("beta2", self.beta2),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (n, p) = (y[0], y[1]);
+        let (alpha1, beta1, alpha2, beta2) = (q[0], q[1], q[2], q[3]);
+        let one = T::from(1.);
+        array![
+            alpha1 * n * (one + beta1 * p),
+            alpha2 * p * (one + beta2 * n)
+        ]
+    }
+}
+
+/// `n' = alpha1 n (1 - (n - beta1 p) / k1)`, `p' = alpha2 p (1 - (p - beta2 n) / k2)`.
+pub struct Mutualism2 {
+    pub alpha1: f64,
+    pub beta1: f64,
+    pub k1: f64,
+    pub alpha2: f64,
+    pub beta2: f64,
+    pub k2: f64,
+}
+
+impl Default for Mutualism2 {
+    fn default() -> Self {
+        Self {
+            alpha1: 15.,
+            beta1: 0.1,
+            k1: 300.,
+            alpha2: 10.0,
+            beta2: 0.01,
+            k2: 400.,
+        }
+    }
+}
+
+impl RightHandSide for Mutualism2 {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("alpha1", self.alpha1),
+            ("beta1", self.beta1),
+            ("k1", self.k1),
+            ("alpha2", self.alpha2),
+            ("beta2", self.beta2),
+            ("k2", self.k2),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (n, p) = (y[0], y[1]);
+        let (alpha1, beta1, k1, alpha2, beta2, k2) = (q[0], q[1], q[2], q[3], q[4], q[5]);
+        let one = T::from(1.);
+        array![
+            alpha1 * n * (one - (n - beta1 * p) / k1),
+            alpha2 * p * (one - (p - beta2 * n) / k2)
+        ]
+    }
+}
+
+/// Unchecked mutualism blows up in finite time, near `t = 0.0056` from this start, so the run stops
+/// short of it.
 pub fn mutualism1(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (alpha1, beta1, alpha2, beta2) = (15., 0.1, 10.0, 0.01);
-    let (n0, p0, tn, n_steps) = (2000., 100., 1., 10000);
-    let dn_by_dt = |_: f64, n: f64, p: f64| alpha1 * n * (1. + beta1 * p);
-    let dp_by_dt = |_: f64, n: f64, p: f64| alpha2 * p * (1. + beta2 * n);
+    let model = Mutualism1::default();
+    let (n0, p0, tn, n_steps) = (2000., 100., 0.005, 10000);
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (nt, pt) = ode_solver.solve(dn_by_dt, dp_by_dt, tn / n_steps as f64, n_steps, &t, n0, p0);
+    let (nt, pt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, n0, p0);
 
     chart_drawer(
         "plots/mutualism1.png",
@@ -19,13 +106,11 @@ pub fn mutualism1(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
 }
 
 pub fn mutualism2(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (alpha1, beta1, k1, alpha2, beta2, k2) = (15., 0.1, 300., 10.0, 0.01, 400.);
+    let model = Mutualism2::default();
     let (n0, p0, tn, n_steps) = (2000., 100., 1., 10000);
-    let dn_by_dt = |_: f64, n: f64, p: f64| alpha1 * n * (1. - (n - beta1 * p) / k1);
-    let dp_by_dt = |_: f64, n: f64, p: f64| alpha2 * p * (1. - (p - beta2 * n) / k2);
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (nt, pt) = ode_solver.solve(dn_by_dt, dp_by_dt, tn / n_steps as f64, n_steps, &t, n0, p0);
+    let (nt, pt) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, n0, p0);
 
     chart_drawer(
         "plots/mutualism2.png",
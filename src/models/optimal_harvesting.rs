@@ -1,6 +1,7 @@
-use super::ChartDrawer;
-use crate::ode_solvers::ODESolver1;
-use ndarray::Array1;
+use super::{BifurcationDiagramDrawer, ChartDrawer};
+use crate::analysis::{continue_equilibria, orbit_diagram, ContinuationOptions};
+use crate::ode_solvers::{ODESolver, ODESolver1};
+use ndarray::{array, Array1};
 
 pub fn optimal_harvesting(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
     let (alpha, h, k) = (1., 2., 10.);
@@ -15,4 +16,39 @@ pub fn optimal_harvesting(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
         "Optimal Harvesting",
         vec![(t, nt, "population")],
     );
+}
+
+/// Equilibria of `optimal_harvesting` against the harvesting effort `h`. The positive branch
+/// `k (1 - h / alpha)` exchanges stability with extinction where they cross at `h = alpha`.
+pub fn optimal_harvesting_bifurcation(
+    ode_solver: ODESolver,
+    chart_drawer: BifurcationDiagramDrawer,
+) {
+    let (alpha, k) = (1., 10.);
+    let (h_min, h_max) = (0., 2.);
+    let dn_by_dt = |n: &Array1<f64>, h: f64| n.mapv(|n| alpha * n * (1. - n / k) - h * n);
+
+    let options = ContinuationOptions::default();
+    let branches = [
+        continue_equilibria(dn_by_dt, &array![k], h_min, (h_min, h_max), &options),
+        continue_equilibria(dn_by_dt, &array![0.], h_min, (h_min, h_max), &options),
+    ];
+
+    let orbits = orbit_diagram(
+        ode_solver,
+        |_, n, h| dn_by_dt(n, h),
+        &array![2.],
+        &Array1::linspace(h_min, h_max, 41),
+        0,
+        0.02,
+        50.,
+        10.,
+    );
+
+    chart_drawer(
+        "plots/optimal_harvesting_bifurcation.png",
+        "Optimal Harvesting - Equilibria against h",
+        &branches,
+        vec![(orbits.maxima.0, orbits.maxima.1, "long-run population")],
+    );
 }
\ No newline at end of file
@@ -0,0 +1,66 @@
+use super::{LogisticPredatorPrey, LotkaVolterra};
+use crate::analysis::{period, refine_cycle, Oscillation, PeriodMethod};
+use crate::ode_solvers::{ODESolver, RightHandSide};
+use ndarray::{array, Array1};
+use std::f64::consts::PI;
+
+/// Prints the period, amplitudes and predator lag of the predator-prey oscillations, and the
+/// `lotka_volterra` orbit through the initial state refined by shooting.
+pub fn oscillation_reports(ode_solver: ODESolver) {
+    let (n0, p0, tn, n_steps) = (2000., 100., 3., 30000);
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let h = tn / n_steps as f64;
+
+    let model = LotkaVolterra::default();
+    let y = ode_solver.solve(|t, y| model.f(t, y), h, n_steps, &t, &array![n0, p0]);
+    println!("Oscillation of lotka_volterra");
+    for method in [
+        PeriodMethod::Peaks,
+        PeriodMethod::PoincareReturn,
+        PeriodMethod::Autocorrelation,
+    ] {
+        let estimate = period(&t, &y.column(0).to_owned(), method);
+        println!(
+            "{:>16}: period {}",
+            format!("{:?}", method),
+            estimate.map_or("not found".to_string(), |p| p.to_string())
+        );
+    }
+    println!(
+        "{:>16}: period {}",
+        "linearised",
+        2. * PI / (model.alpha1 * model.alpha2).sqrt()
+    );
+    let oscillation = Oscillation::detect(&t, &y, PeriodMethod::PoincareReturn, 1e-3)
+        .expect("lotka_volterra orbits are closed");
+    println!(
+        "amplitude preys {:.3}, predator {:.3}; predator peaks {:.5} ({:.1} degrees) after preys",
+        oscillation.amplitude[0],
+        oscillation.amplitude[1],
+        oscillation.phase_lag[1],
+        360. * oscillation.phase_lag[1] / oscillation.period
+    );
+    let cycle = refine_cycle(
+        ode_solver,
+        |y| model.f(0., y),
+        &array![n0, p0],
+        oscillation.period,
+        0,
+        2000,
+    )
+    .expect("shooting did not converge");
+    println!(
+        "shooting: orbit through ({}, {}) with period {}, residual {:e}",
+        cycle.y_0[0], cycle.y_0[1], cycle.period, cycle.residual
+    );
+
+    let model = LogisticPredatorPrey::default();
+    let y = ode_solver.solve(|t, y| model.f(t, y), h, n_steps, &t, &array![n0, p0]);
+    match Oscillation::detect(&t, &y, PeriodMethod::Peaks, 1e-3) {
+        Some(oscillation) => println!(
+            "logistic_predator_prey oscillates with period {}",
+            oscillation.period
+        ),
+        None => println!("logistic_predator_prey: no sustained oscillation"),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use super::{CompetitionModel, LogisticPredatorPrey, LotkaVolterra, PhasePortraitDrawer};
+use crate::analysis::{initial_condition_grid, PhasePortrait};
+use crate::ode_solvers::ODESolver2;
+
+/// Phase portraits of the predator-prey and competition models with trajectories from a grid of
+/// initial conditions, written once per `(drawer, extension)` pair.
+pub fn phase_portraits(ode_solver: ODESolver2, chart_drawers: &[(PhasePortraitDrawer, &str)]) {
+    let (tn, n_steps) = (2., 20000);
+
+    let lotka_volterra = LotkaVolterra::default();
+    let bounds = [(0., 3000.), (0., 600.)];
+    let portrait = PhasePortrait::from_rhs(&lotka_volterra, bounds, ["preys", "predator"])
+        .with_trajectories(ode_solver, &initial_condition_grid(bounds, 3), tn, n_steps);
+    for (chart_drawer, extension) in chart_drawers {
+        chart_drawer(
+            &format!("plots/lotka_volterra_phase_portrait.{}", extension),
+            "Predator Prey - Lotka Volterra Phase Portrait",
+            &portrait,
+        );
+    }
+
+    let logistic_predator_prey = LogisticPredatorPrey::default();
+    let bounds = [(0., 250.), (0., 15.)];
+    let portrait = PhasePortrait::from_rhs(&logistic_predator_prey, bounds, ["preys", "predator"])
+        .with_trajectories(ode_solver, &initial_condition_grid(bounds, 4), tn, n_steps);
+    for (chart_drawer, extension) in chart_drawers {
+        chart_drawer(
+            &format!("plots/logistic_predator_prey_phase_portrait.{}", extension),
+            "Logistic Predator Prey Phase Portrait",
+            &portrait,
+        );
+    }
+
+    let competition_model = CompetitionModel::default();
+    let bounds = [(0., 800.), (0., 800.)];
+    let portrait = PhasePortrait::from_rhs(&competition_model, bounds, ["n", "p"])
+        .with_trajectories(ode_solver, &initial_condition_grid(bounds, 4), tn, n_steps);
+    for (chart_drawer, extension) in chart_drawers {
+        chart_drawer(
+            &format!("plots/competition_model_phase_portrait.{}", extension),
+            "Competition Model Phase Portrait",
+            &portrait,
+        );
+    }
+}
\ No newline at end of file
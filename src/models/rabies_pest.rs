@@ -1,15 +1,178 @@
-use crate::models::ChartDrawer;
-use crate::ode_solvers::ODESolver2;
-use ndarray::Array1;
+use crate::dual::Real;
+use crate::fitting::{
+    FitProblem, LevenbergMarquardtOptions, Likelihood, McmcOptions, Observations, Posterior, Prior,
+};
+use crate::gillespie::{ReactionNetwork, SSASolver};
+use crate::models::{BandChartDrawer, ChainDrawer, ChartDrawer};
+use crate::ode_solvers::{ODESolver, ODESolver2, PatankarSolver, RightHandSide};
+use crate::sde_solvers::EnsembleStatistics;
+use ndarray::{array, Array1, Array2};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// `s' = r (s + i) (1 - s / k) - beta s i`, `i' = beta s i - u i`.
+pub struct RabiesPest1 {
+    pub r: f64,
+    pub beta: f64,
+    pub u: f64,
+    pub k: f64,
+}
+
+impl Default for RabiesPest1 {
+    fn default() -> Self {
+        Self {
+            r: 0.1,
+            beta: 0.1,
+            u: 10.,
+            k: 100.,
+        }
+    }
+}
+
+impl RabiesPest1 {
+    pub fn tuple(&self) -> (f64, f64, f64, f64) {
+        (self.r, self.beta, self.u, self.k)
+    }
+}
+
+impl RightHandSide for RabiesPest1 {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("r", self.r),
+            ("beta", self.beta),
+            ("u", self.u),
+            ("k", self.k),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (s, i) = (y[0], y[1]);
+        let (r, beta, u, k) = (q[0], q[1], q[2], q[3]);
+        array![
+            r * (s + i) * (T::from(1.) - s / k) - beta * s * i,
+            beta * s * i - u * i
+        ]
+    }
+}
+
+/// `rabies_pest1` with culling of both classes at rate `c`.
+pub struct RabiesPest2 {
+    pub r: f64,
+    pub beta: f64,
+    pub u: f64,
+    pub k: f64,
+    pub c: f64,
+}
+
+impl Default for RabiesPest2 {
+    fn default() -> Self {
+        Self {
+            r: 0.1,
+            beta: 0.1,
+            u: 10.,
+            k: 100.,
+            c: 10.,
+        }
+    }
+}
+
+impl RabiesPest2 {
+    pub fn tuple(&self) -> (f64, f64, f64, f64, f64) {
+        (self.r, self.beta, self.u, self.k, self.c)
+    }
+
+    /// Births `r (s + i)` of susceptibles and new infections `beta s i`.
+    pub fn production(&self, y: &Array1<f64>) -> Array1<f64> {
+        let (s, i) = (y[0], y[1]);
+        array![self.r * (s + i), self.beta * s * i]
+    }
+
+    /// Crowding, infection and culling of susceptibles, death and culling of infectives.
+    pub fn destruction(&self, y: &Array1<f64>) -> Array1<f64> {
+        let (s, i) = (y[0], y[1]);
+        array![
+            self.r * (s + i) * s / self.k + self.beta * s * i + self.c * s,
+            (self.u + self.c) * i
+        ]
+    }
+}
+
+impl RightHandSide for RabiesPest2 {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("r", self.r),
+            ("beta", self.beta),
+            ("u", self.u),
+            ("k", self.k),
+            ("c", self.c),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (s, i) = (y[0], y[1]);
+        let (r, beta, u, k, c) = (q[0], q[1], q[2], q[3], q[4]);
+        array![
+            r * (s + i) * (T::from(1.) - s / k) - beta * s * i - c * s,
+            beta * s * i - u * i - c * i
+        ]
+    }
+}
+
+/// `rabies_pest1` with the contact rate scaled by `1 - v` for vaccination.
+pub struct RabiesPest3 {
+    pub r: f64,
+    pub beta: f64,
+    pub u: f64,
+    pub k: f64,
+    pub v: f64,
+}
+
+impl Default for RabiesPest3 {
+    fn default() -> Self {
+        Self {
+            r: 0.1,
+            beta: 0.1,
+            u: 10.,
+            k: 100.,
+            v: 10.,
+        }
+    }
+}
+
+impl RabiesPest3 {
+    pub fn tuple(&self) -> (f64, f64, f64, f64, f64) {
+        (self.r, self.beta, self.u, self.k, self.v)
+    }
+}
+
+impl RightHandSide for RabiesPest3 {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("r", self.r),
+            ("beta", self.beta),
+            ("u", self.u),
+            ("k", self.k),
+            ("v", self.v),
+        ]
+    }
+
+    fn eval<T: Real>(&self, _: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let (s, i) = (y[0], y[1]);
+        let (r, beta, u, k, v) = (q[0], q[1], q[2], q[3], q[4]);
+        let one = T::from(1.);
+        array![
+            r * (s + i) * (one - s / k) - beta * (one - v) * s * i,
+            beta * (one - v) * s * i - u * i
+        ]
+    }
+}
 
 pub fn rabies_pest1(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (r, beta, u, k) = (0.1, 0.1, 10., 100.);
+    let model = RabiesPest1::default();
     let (s0, i0, tn, n_steps) = (100., 10., 1., 10000);
-    let ds_by_dt = |_: f64, s: f64, i: f64| r * (s + i) * (1. - s / k) - beta * s * i;
-    let di_by_dt = |_: f64, s: f64, i: f64| beta * s * i - u * i;
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (st, it) = ode_solver.solve(ds_by_dt, di_by_dt, tn / n_steps as f64, n_steps, &t, s0, i0);
+    let (st, it) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, s0, i0);
 
     chart_drawer(
         "plots/rabies_pest1.png",
@@ -19,13 +182,11 @@ pub fn rabies_pest1(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
 }
 
 pub fn rabies_pest2(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (r, beta, u, k, c) = (0.1, 0.1, 10., 100., 10.);
+    let model = RabiesPest2::default();
     let (s0, i0, tn, n_steps) = (100., 10., 1., 10000);
-    let ds_by_dt = |_: f64, s: f64, i: f64| r * (s + i) * (1. - s / k) - beta * s * i - c * s;
-    let di_by_dt = |_: f64, s: f64, i: f64| beta * s * i - u * i - c * i;
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (st, it) = ode_solver.solve(ds_by_dt, di_by_dt, tn / n_steps as f64, n_steps, &t, s0, i0);
+    let (st, it) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, s0, i0);
 
     chart_drawer(
         "plots/rabies_pest2.png",
@@ -34,18 +195,210 @@ pub fn rabies_pest2(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
     )
 }
 
+/// `rabies_pest2` with ten steps of 0.1, split into production and destruction for a Patankar
+/// scheme. Forward Euler takes both classes negative in the first step at this size.
+pub fn rabies_pest2_patankar(patankar_solver: PatankarSolver, chart_drawer: ChartDrawer) {
+    let model = RabiesPest2::default();
+    let (s0, i0, tn, n_steps) = (100., 10., 1., 10);
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let y = patankar_solver.solve(
+        |_, y: &Array1<f64>| model.production(y),
+        |_, y: &Array1<f64>| model.destruction(y),
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &array![s0, i0],
+    );
+
+    chart_drawer(
+        "plots/rabies_pest2_patankar.png",
+        "Rabies Pest 2 - Patankar Steps",
+        vec![
+            (t.clone(), y.column(0).to_owned(), "susceptible"),
+            (t, y.column(1).to_owned(), "infective"),
+        ],
+    )
+}
+
 pub fn rabies_pest3(ode_solver: ODESolver2, chart_drawer: ChartDrawer) {
-    let (r, beta, u, k, v) = (0.1, 0.1, 10., 100., 10.);
+    let model = RabiesPest3::default();
     let (s0, i0, tn, n_steps) = (100., 10., 1., 10000);
-    let ds_by_dt = |_: f64, s: f64, i: f64| r * (s + i) * (1. - s / k) - beta * (1. - v) * s * i;
-    let di_by_dt = |_: f64, s: f64, i: f64| beta * (1. - v) * s * i - u * i;
 
     let t = Array1::linspace(0., tn, n_steps);
-    let (st, it) = ode_solver.solve(ds_by_dt, di_by_dt, tn / n_steps as f64, n_steps, &t, s0, i0);
+    let (st, it) = ode_solver.solve_rhs(&model, tn / n_steps as f64, n_steps, &t, s0, i0);
 
     chart_drawer(
         "plots/rabies_pest3.png",
         "Rabies Pest 3",
         vec![(t.clone(), st, "susceptible"), (t, it, "infective")],
     )
+}
+
+/// `rabies_pest1` with individual susceptibles and infectives, where the infection can die out.
+pub fn stochastic_rabies_pest1(ssa_solver: SSASolver, chart_drawer: BandChartDrawer) {
+    let network = ReactionNetwork::rabies(RabiesPest1::default().tuple());
+    let (s0, i0, tn, n_points, n_paths) = (100., 10., 1., 201, 200);
+
+    let paths = ssa_solver.trajectories(&network, &array![s0, i0], tn, n_paths);
+    let extinct = paths
+        .iter()
+        .filter(|path| path.extinction_time(1).is_some())
+        .count();
+    println!("infection died out in {} of {} runs", extinct, n_paths);
+
+    let t = Array1::linspace(0., tn, n_points);
+    let resampled: Vec<_> = paths.iter().map(|path| path.resample(&t)).collect();
+    let stats = EnsembleStatistics::from_paths(&resampled, &[0.05, 0.95]);
+
+    let band = |j: usize, label| {
+        (
+            t.clone(),
+            stats.quantile(0.05).column(j).to_owned(),
+            stats.quantile(0.95).column(j).to_owned(),
+            label,
+        )
+    };
+    chart_drawer(
+        "plots/stochastic_rabies_pest1.png",
+        "Rabies Pest 1 - Demographic Stochasticity",
+        vec![
+            band(0, "susceptible 90% band"),
+            band(1, "infective 90% band"),
+        ],
+        vec![
+            (
+                t.clone(),
+                stats.mean.column(0).to_owned(),
+                "mean susceptible",
+            ),
+            (t.clone(), stats.mean.column(1).to_owned(), "mean infective"),
+        ],
+    )
+}
+
+/// Posterior of the contact rate `beta` and the death rate `u` of `rabies_pest1` from counts of
+/// susceptibles and infectives. The counts are synthetic, Poisson draws about the model with its
+/// default parameters, so the posterior can be checked against the truth. Adaptive Metropolis
+/// starts from the least-squares estimate and an ensemble sampler is run for comparison.
+pub fn rabies_pest1_posterior(
+    ode_solver: ODESolver,
+    trace_drawer: ChainDrawer,
+    corner_drawer: ChainDrawer,
+    band_drawer: BandChartDrawer,
+) {
+    let truth = RabiesPest1::default();
+    let (s0, i0, tn, n_obs) = (100., 10., 1., 21);
+    let t = Array1::linspace(0., tn, n_obs);
+    let n_steps = 200;
+    let y = ode_solver.solve_rhs(
+        &truth,
+        tn / n_steps as f64,
+        n_steps,
+        &Array1::linspace(0., tn, n_steps + 1),
+        &array![s0, i0],
+    );
+    let mut rng = StdRng::seed_from_u64(7);
+    let observations = Observations {
+        y: Array2::from_shape_fn((n_obs, 2), |(i, j)| {
+            Likelihood::Poisson.sample(y[[i * n_steps / (n_obs - 1), j]], &mut rng)
+        }),
+        t,
+        names: vec!["susceptible".to_string(), "infective".to_string()],
+    };
+
+    let guess = RabiesPest1 {
+        beta: 0.2,
+        u: 5.,
+        ..RabiesPest1::default()
+    };
+    let problem = FitProblem::new(ode_solver, &guess, &observations, &array![s0, i0], 0.005)
+        .observe("susceptible", 0)
+        .observe("infective", 1)
+        .free("beta", (1e-3, 1.))
+        .free("u", (0.1, 50.));
+    let fit = problem.levenberg_marquardt(&LevenbergMarquardtOptions::default());
+    fit.print_report("rabies_pest1 least squares");
+
+    let posterior = Posterior::new(&problem, Likelihood::Poisson)
+        .with_prior(
+            "beta",
+            Prior::LogNormal {
+                mu: 0.1f64.ln(),
+                sigma: 1.,
+            },
+        )
+        .with_prior(
+            "u",
+            Prior::LogNormal {
+                mu: 10f64.ln(),
+                sigma: 1.,
+            },
+        );
+    let options = McmcOptions::default();
+    let chain = posterior.metropolis_hastings(&fit.estimates, &options);
+    chain.print_summary("rabies_pest1 posterior by adaptive Metropolis");
+    posterior
+        .ensemble(
+            &fit.estimates,
+            8,
+            &McmcOptions {
+                n_samples: 5_000,
+                burn_in: 1_000,
+                ..options
+            },
+        )
+        .print_summary("rabies_pest1 posterior by ensemble sampler");
+
+    trace_drawer(
+        "plots/rabies_pest1_trace.png",
+        "Rabies Pest 1 - MCMC Trace",
+        &chain,
+    );
+    corner_drawer(
+        "plots/rabies_pest1_corner.png",
+        "Rabies Pest 1 - Posterior",
+        &chain,
+    );
+
+    let t = Array1::linspace(0., tn, 101);
+    let predictive = posterior.predictive(&chain, &t, 500, &[0.025, 0.5, 0.975], 11);
+    let band = |j: usize, label| {
+        (
+            t.clone(),
+            predictive.quantile(0.025).column(j).to_owned(),
+            predictive.quantile(0.975).column(j).to_owned(),
+            label,
+        )
+    };
+    band_drawer(
+        "plots/rabies_pest1_posterior_predictive.png",
+        "Rabies Pest 1 - Posterior Predictive",
+        vec![
+            band(0, "susceptible 95% band"),
+            band(1, "infective 95% band"),
+        ],
+        vec![
+            (
+                t.clone(),
+                predictive.quantile(0.5).column(0).to_owned(),
+                "median susceptible",
+            ),
+            (
+                t.clone(),
+                predictive.quantile(0.5).column(1).to_owned(),
+                "median infective",
+            ),
+            (
+                observations.t.clone(),
+                observations.y.column(0).to_owned(),
+                "observed susceptible",
+            ),
+            (
+                observations.t.clone(),
+                observations.y.column(1).to_owned(),
+                "observed infective",
+            ),
+        ],
+    );
 }
\ No newline at end of file
@@ -1,11 +1,54 @@
-use super::ChartDrawer;
-use crate::ode_solvers::ODESolver1;
-use ndarray::Array1;
+use super::{BandChartDrawer, ChartDrawer};
+use crate::analysis::{orbit_diagram, poincare_section, Section};
+use crate::dual::Real;
+use crate::ode_solvers::{AdaptiveOptions, ODESolver, ODESolver1, RightHandSide};
+use crate::sde_solvers::SDESolver;
+use ndarray::{array, concatenate, s, Array1, Axis};
+use std::f64::consts::PI;
+
+/// Logistic growth at rate `alpha` under a carrying capacity `k / (1 + beta cos(gamma t))` that
+/// swings with the seasons.
+pub struct SeasonalCapacity {
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub k: f64,
+}
+
+impl Default for SeasonalCapacity {
+    fn default() -> Self {
+        Self {
+            alpha: 0.8,
+            beta: 0.5,
+            gamma: 1.5,
+            k: 10.,
+        }
+    }
+}
+
+impl RightHandSide for SeasonalCapacity {
+    fn parameters(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("alpha", self.alpha),
+            ("beta", self.beta),
+            ("gamma", self.gamma),
+            ("k", self.k),
+        ]
+    }
+
+    fn eval<T: Real>(&self, t: f64, y: &Array1<T>, q: &Array1<T>) -> Array1<T> {
+        let n = y[0];
+        let (alpha, beta, gamma, k) = (q[0], q[1], q[2], q[3]);
+        let one = T::from(1.);
+        let season = (gamma * T::from(t)).cos();
+        array![alpha * n * (one - n * (one + beta * season) / k)]
+    }
+}
 
 pub fn seasonal_capacity(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
-    let (alpha, beta, gamma, k) = (0.8, 0.5, 1.5, 10.);
+    let model = SeasonalCapacity::default();
     let (n0, tn, n_steps) = (2., 10., 1000000);
-    let dn_by_dt = |t: f64, n: f64| alpha * n * (1. - n * (1. + beta * f64::cos(gamma * t)) / k);
+    let dn_by_dt = |t: f64, n: f64| model.f(t, &array![n])[0];
 
     let t = Array1::linspace(0., tn, n_steps);
     let nt = ode_solver.solve(dn_by_dt, tn / n_steps as f64, n_steps, &t, n0);
@@ -15,4 +58,147 @@ pub fn seasonal_capacity(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
         "Seasonal Capacity Model",
         vec![(t, nt, "population")],
     );
+}
+
+/// `seasonal_capacity` with a multistep method, which costs one right-hand side evaluation per
+/// step for Adams-Bashforth. A thousand steps of `0.01` already resolve the seasonal swing.
+pub fn seasonal_capacity_multistep(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
+    let model = SeasonalCapacity::default();
+    let (n0, tn, n_steps) = (2., 10., 1000);
+    let dn_by_dt = |t: f64, n: f64| model.f(t, &array![n])[0];
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let nt = ode_solver.solve(dn_by_dt, tn / n_steps as f64, n_steps, &t, n0);
+
+    chart_drawer(
+        "plots/seasonal_capacity_model_multistep.png",
+        "Seasonal Capacity Model - Multistep",
+        vec![(t, nt, "population")],
+    );
+}
+
+/// `seasonal_capacity` with an embedded pair choosing the steps. `h_max` keeps enough points per
+/// season for the chart.
+pub fn seasonal_capacity_adaptive(ode_solver: ODESolver1, chart_drawer: ChartDrawer) {
+    let model = SeasonalCapacity::default();
+    let (n0, tn) = (2., 10.);
+    let dn_by_dt = |t: f64, n: f64| model.f(t, &array![n])[0];
+
+    let options = AdaptiveOptions {
+        h_max: tn / 500.,
+        ..AdaptiveOptions::default()
+    };
+    let (t, nt) = ode_solver
+        .solve_adaptive(dn_by_dt, (0., tn), n0, &options)
+        .expect("the population stays bounded over the span");
+
+    chart_drawer(
+        "plots/seasonal_capacity_model_adaptive.png",
+        "Seasonal Capacity Model - Adaptive Steps",
+        vec![(t, nt, "population")],
+    );
+}
+
+/// Long-run maxima and minima of `seasonal_capacity` against the seasonal amplitude `beta`. The
+/// model is forced, so it has no equilibria to continue, but the sweep shows the periodic
+/// response growing out of the constant carrying capacity at `beta = 0`.
+pub fn seasonal_capacity_orbit_diagram(ode_solver: ODESolver, chart_drawer: ChartDrawer) {
+    let dn_by_dt = |t: f64, n: &Array1<f64>, beta: f64| {
+        let model = SeasonalCapacity {
+            beta,
+            ..SeasonalCapacity::default()
+        };
+        model.f(t, n)
+    };
+
+    let orbits = orbit_diagram(
+        ode_solver,
+        dn_by_dt,
+        &array![2.],
+        &Array1::linspace(0., 0.9, 46),
+        0,
+        0.02,
+        60.,
+        20.,
+    );
+
+    chart_drawer(
+        "plots/seasonal_capacity_orbit_diagram.png",
+        "Seasonal Capacity Model - Long-run Extremes against beta",
+        vec![
+            (orbits.maxima.0, orbits.maxima.1, "maxima"),
+            (orbits.minima.0, orbits.minima.1, "minima"),
+        ],
+    );
+}
+
+/// The `seasonal_capacity` population sampled once per forcing period `2 pi / gamma`, plotted as
+/// pairs `(n_k, n_k+1)` from a spread of initial populations. The points trace the stroboscopic
+/// map, whose fixed point is the periodic response.
+pub fn seasonal_capacity_stroboscopic_map(ode_solver: ODESolver, chart_drawer: ChartDrawer) {
+    let model = SeasonalCapacity::default();
+    let (h, n_periods) = (0.01, 8.);
+    let dn_by_dt = |t: f64, n: &Array1<f64>| model.f(t, n);
+    let section = Section::Stroboscopic {
+        period: 2. * PI / model.gamma,
+        phase: 0.,
+    };
+    let n_steps = (n_periods * 2. * PI / model.gamma / h).ceil() as usize;
+
+    let (mut n_k, mut n_k1) = (Array1::zeros(0), Array1::zeros(0));
+    for n0 in Array1::linspace(0.5, 25., 25) {
+        let strobe = poincare_section(
+            ode_solver,
+            dn_by_dt,
+            &section,
+            h,
+            n_steps,
+            0.,
+            &array![n0],
+            0.,
+        );
+        // the initial population is the first sample of the map
+        let samples = concatenate![Axis(0), array![n0], strobe.y.column(0)];
+        let n = samples.len() - 1;
+        n_k = concatenate![Axis(0), n_k, samples.slice(s![..n])];
+        n_k1 = concatenate![Axis(0), n_k1, samples.slice(s![1..])];
+    }
+
+    chart_drawer(
+        "plots/seasonal_capacity_stroboscopic_map.png",
+        "Seasonal Capacity Model - Stroboscopic Map",
+        vec![(n_k, n_k1, "(n_k, n_k+1)")],
+    );
+}
+
+/// Seasonal capacity model with additive environmental noise of intensity `sigma`.
+pub fn stochastic_seasonal_capacity(sde_solver: SDESolver, chart_drawer: BandChartDrawer) {
+    let (model, sigma) = (SeasonalCapacity::default(), 0.3);
+    let (n0, tn, n_steps, n_paths) = (2., 10., 2000, 200);
+    let drift = |t: f64, n: &Array1<f64>| model.f(t, n);
+    let diffusion = |_: f64, n: &Array1<f64>| Array1::from_elem(n.len(), sigma);
+
+    let t = Array1::linspace(0., tn, n_steps + 1);
+    let stats = sde_solver.ensemble(
+        drift,
+        diffusion,
+        tn / n_steps as f64,
+        n_steps,
+        &t,
+        &array![n0],
+        n_paths,
+        &[0.05, 0.95],
+    );
+
+    chart_drawer(
+        "plots/stochastic_seasonal_capacity_model.png",
+        "Seasonal Capacity Model with Additive Noise",
+        vec![(
+            t.clone(),
+            stats.quantile(0.05).column(0).to_owned(),
+            stats.quantile(0.95).column(0).to_owned(),
+            "90% band",
+        )],
+        vec![(t, stats.mean.column(0).to_owned(), "mean population")],
+    );
 }
\ No newline at end of file
@@ -0,0 +1,292 @@
+use super::positivity::{clamp_negative, goes_negative};
+use super::ODESolver;
+use ndarray::{Array1, Array2, Axis};
+use std::fmt;
+
+#[derive(Copy, Clone)]
+pub struct AdaptiveOptions {
+    pub atol: f64,
+    pub rtol: f64,
+    pub h_init: Option<f64>,
+    pub h_min: f64,
+    pub h_max: f64,
+    pub max_steps: usize,
+}
+
+impl Default for AdaptiveOptions {
+    fn default() -> Self {
+        Self {
+            atol: 1e-8,
+            rtol: 1e-6,
+            h_init: None,
+            h_min: 1e-12,
+            h_max: f64::INFINITY,
+            max_steps: 1_000_000,
+        }
+    }
+}
+
+impl AdaptiveOptions {
+    pub fn with_tolerances(atol: f64, rtol: f64) -> Self {
+        Self {
+            atol,
+            rtol,
+            ..Self::default()
+        }
+    }
+
+    /// Weighted RMS norm of `err` scaled by `atol + rtol * max(|y|, |y_next|)`.
+    pub fn error_norm(&self, err: &Array1<f64>, y: &Array1<f64>, y_next: &Array1<f64>) -> f64 {
+        let sum: f64 = err
+            .iter()
+            .zip(y.iter().zip(y_next))
+            .map(|(e, (a, b))| {
+                let scale = self.atol + self.rtol * f64::max(a.abs(), b.abs());
+                (e / scale).powi(2)
+            })
+            .sum();
+        (sum / err.len() as f64).sqrt()
+    }
+
+    /// Hairer's starting step heuristic, used when `h_init` is not set.
+    pub fn initial_step<F>(
+        &self,
+        f: &F,
+        t_0: f64,
+        y_0: &Array1<f64>,
+        f_0: &Array1<f64>,
+        order: usize,
+    ) -> f64
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        if let Some(h) = self.h_init {
+            return h;
+        }
+        let zeros = Array1::zeros(y_0.len());
+        let d_0 = self.error_norm(y_0, &zeros, y_0);
+        let d_1 = self.error_norm(f_0, &zeros, y_0);
+        let h_0 = if d_0 < 1e-5 || d_1 < 1e-5 {
+            1e-6
+        } else {
+            0.01 * d_0 / d_1
+        };
+
+        let y_1 = y_0 + &(f_0 * h_0);
+        let f_1 = f(t_0 + h_0, &y_1);
+        let d_2 = self.error_norm(&(&f_1 - f_0), &zeros, y_0) / h_0;
+        let h_1 = if f64::max(d_1, d_2) <= 1e-15 {
+            f64::max(1e-6, h_0 * 1e-3)
+        } else {
+            (0.01 / f64::max(d_1, d_2)).powf(1. / (order as f64 + 1.))
+        };
+        f64::min(100. * h_0, h_1).clamp(self.h_min, self.h_max)
+    }
+}
+
+/// The step size needed to meet the tolerances fell below `h_min` at `t`, or the solution is no
+/// longer finite there. Usually the solution blows up near `t`, or the problem is too stiff for an
+/// explicit pair.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StepSizeUnderflow {
+    pub t: f64,
+    pub h: f64,
+}
+
+impl fmt::Display for StepSizeUnderflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "step size underflow at t = {} with h = {:e}",
+            self.t, self.h
+        )
+    }
+}
+
+impl std::error::Error for StepSizeUnderflow {}
+
+/// An accepted step from `t` to `(t + h, y_next)` with its stages and `f(t + h, y_next)`.
+pub(super) struct AcceptedStep<'a> {
+    pub t: f64,
+    pub h: f64,
+    pub y_next: &'a Array1<f64>,
+    pub k: &'a [Array1<f64>],
+    pub f_next: &'a Array1<f64>,
+}
+
+impl ODESolver<'_> {
+    /// Integrates over `t_span` with an embedded pair, choosing the step from the local error
+    /// estimate with a PI controller. Returns the accepted time points and the states there, or
+    /// an error once a step of `h_min` still fails the tolerances or is not finite.
+    pub fn solve_adaptive<F>(
+        self,
+        f: F,
+        t_span: (f64, f64),
+        y_0: &Array1<f64>,
+        options: &AdaptiveOptions,
+    ) -> Result<(Array1<f64>, Array2<f64>), StepSizeUnderflow>
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let mut ts = vec![t_span.0];
+        let mut ys = vec![y_0.clone()];
+        self.integrate_adaptive(&f, t_span, y_0, options, |step| {
+            ts.push(step.t + step.h);
+            ys.push(step.y_next.clone());
+        })?;
+
+        let views: Vec<_> = ys.iter().map(|y| y.view()).collect();
+        Ok((Array1::from(ts), ndarray::stack(Axis(0), &views).unwrap()))
+    }
+
+    pub(super) fn integrate_adaptive<F, S>(
+        self,
+        f: &F,
+        t_span: (f64, f64),
+        y_0: &Array1<f64>,
+        options: &AdaptiveOptions,
+        mut on_step: S,
+    ) -> Result<(), StepSizeUnderflow>
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        S: FnMut(AcceptedStep),
+    {
+        let tableau = self
+            .method
+            .tableau()
+            .filter(|tableau| tableau.b_hat.is_some())
+            .expect("adaptive stepping needs an explicit embedded Runge-Kutta pair");
+
+        let (t_0, t_end) = t_span;
+        let q = tableau.order.min(tableau.embedded_order) as f64 + 1.;
+        let (alpha, beta) = (0.7 / q, 0.4 / q);
+        let (safety, min_factor, max_factor) = (0.9, 0.2, 5.);
+
+        let mut t = t_0;
+        let mut y = y_0.clone();
+        let mut k_1 = f(t, &y);
+        let mut h = options.initial_step(f, t, &y, &k_1, tableau.order);
+        let mut err_prev: f64 = 1.;
+        let mut rejected = false;
+        let mut n_accepted = 0;
+
+        while t < t_end {
+            assert!(
+                n_accepted < options.max_steps,
+                "exceeded {} steps",
+                options.max_steps
+            );
+            if t + h > t_end {
+                h = t_end - t;
+            }
+
+            let k = tableau.stage_derivatives_from(f, t, &y, h, k_1.clone());
+            let y_next = tableau.step_from_stages(&y, h, &k);
+            let err = tableau.error_estimate(h, &k).unwrap();
+            let err_norm = options.error_norm(&err, &y, &y_next);
+            let finite = err_norm.is_finite() && y_next.iter().all(|y_i| y_i.is_finite());
+            let negative = self.positive && goes_negative(&y, &y_next);
+
+            if finite && err_norm <= 1. && !negative {
+                let (y_next, clamped) = if self.positive {
+                    let clamped = clamp_negative(&y, y_next.clone());
+                    let changed = clamped != y_next;
+                    (clamped, changed)
+                } else {
+                    (y_next, false)
+                };
+                let factor = if err_norm == 0. {
+                    max_factor
+                } else {
+                    safety * err_norm.powf(-alpha) * err_prev.powf(beta)
+                };
+                let factor = if rejected {
+                    factor.clamp(min_factor, 1.)
+                } else {
+                    factor.clamp(min_factor, max_factor)
+                };
+
+                k_1 = if tableau.is_fsal() && !clamped {
+                    k.last().unwrap().clone()
+                } else {
+                    f(t + h, &y_next)
+                };
+                on_step(AcceptedStep {
+                    t,
+                    h,
+                    y_next: &y_next,
+                    k: &k,
+                    f_next: &k_1,
+                });
+                t += h;
+                y = y_next;
+                n_accepted += 1;
+
+                err_prev = err_norm.max(1e-4);
+                rejected = false;
+                h = (h * factor).clamp(options.h_min, options.h_max);
+            } else {
+                if h <= options.h_min {
+                    return Err(StepSizeUnderflow { t, h });
+                }
+                let factor = if !finite {
+                    min_factor
+                } else if negative && err_norm <= 1. {
+                    0.5
+                } else {
+                    (safety * err_norm.powf(-1. / q)).clamp(min_factor, 1.)
+                };
+                rejected = true;
+                h = (h * factor).max(options.h_min);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+
+    #[test]
+    fn finite_time_blow_up_is_a_step_size_underflow() {
+        // y' = y^2 from y(0) = 1 is 1 / (1 - t), which blows up at t = 1
+        let result = ODESolver::new(SolverMethod::DormandPrince54).solve_adaptive(
+            |_, y| y * y,
+            (0., 2.),
+            &array![1.],
+            &AdaptiveOptions::default(),
+        );
+        let underflow = result.expect_err("the run should not reach t = 2");
+        assert!((underflow.t - 1.).abs() < 1e-3, "{}", underflow);
+        assert!(underflow.h <= AdaptiveOptions::default().h_min);
+    }
+
+    #[test]
+    fn embedded_pairs_meet_their_tolerance_on_exponential_decay() {
+        let options = AdaptiveOptions {
+            atol: 1e-8,
+            rtol: 1e-6,
+            ..AdaptiveOptions::default()
+        };
+        for (name, method) in [
+            ("BogackiShampine32", SolverMethod::BogackiShampine32),
+            ("Fehlberg45", SolverMethod::Fehlberg45),
+            ("CashKarp54", SolverMethod::CashKarp54),
+            ("DormandPrince54", SolverMethod::DormandPrince54),
+        ] {
+            let (t, y) = ODESolver::new(method)
+                .solve_adaptive(|_, y| -y, (0., 5.), &array![1.], &options)
+                .unwrap();
+            assert_eq!(t[t.len() - 1], 5.);
+            let error = t
+                .iter()
+                .zip(y.column(0))
+                .map(|(&t, &y)| (y - (-t).exp()).abs())
+                .fold(0., f64::max);
+            assert!(error < 1e-6, "{}: {:e}", name, error);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,473 @@
+use ndarray::Array1;
+
+/// Coefficients of an explicit Runge-Kutta method. `a` is stored as ragged rows, row `i`
+/// holding the `i` coefficients below the diagonal, so every tableau is explicit by shape.
+/// The built-in tableaux borrow `'static` data; a custom one can borrow coefficients built at
+/// run time.
+#[derive(Copy, Clone, Debug)]
+pub struct ButcherTableau<'a> {
+    pub name: &'a str,
+    pub a: &'a [&'a [f64]],
+    pub b: &'a [f64],
+    pub c: &'a [f64],
+    pub order: usize,
+    pub b_hat: Option<&'a [f64]>,
+    pub embedded_order: usize,
+    /// Weights `d` of a method-specific continuous extension, see `DenseSolution`.
+    pub dense_output: Option<&'a [f64]>,
+}
+
+impl<'a> ButcherTableau<'a> {
+    pub const fn new(
+        name: &'a str,
+        a: &'a [&'a [f64]],
+        b: &'a [f64],
+        c: &'a [f64],
+        order: usize,
+    ) -> Self {
+        assert!(a.len() == b.len() && b.len() == c.len());
+        let mut i = 0;
+        while i < a.len() {
+            assert!(a[i].len() == i);
+            i += 1;
+        }
+        Self {
+            name,
+            a,
+            b,
+            c,
+            order,
+            b_hat: None,
+            embedded_order: 0,
+            dense_output: None,
+        }
+    }
+
+    /// An embedded pair: `b` advances the solution, `b_hat` gives the error estimate.
+    pub const fn embedded(
+        name: &'a str,
+        a: &'a [&'a [f64]],
+        b: &'a [f64],
+        b_hat: &'a [f64],
+        c: &'a [f64],
+        order: usize,
+        embedded_order: usize,
+    ) -> Self {
+        assert!(b_hat.len() == b.len());
+        let tableau = Self::new(name, a, b, c, order);
+        Self {
+            b_hat: Some(b_hat),
+            embedded_order,
+            ..tableau
+        }
+    }
+
+    pub const fn with_dense_output(self, d: &'a [f64]) -> Self {
+        assert!(d.len() == self.b.len());
+        Self {
+            dense_output: Some(d),
+            ..self
+        }
+    }
+
+    pub fn stages(&self) -> usize {
+        self.b.len()
+    }
+
+    /// Stage derivatives `k_i = f(t + c_i h, y + h * sum_j a_ij k_j)`.
+    pub fn stage_derivatives<F>(&self, f: &F, t: f64, y: &Array1<f64>, h: f64) -> Vec<Array1<f64>>
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        self.stage_derivatives_from(f, t, y, h, f(t, y))
+    }
+
+    /// Same as `stage_derivatives` with `k_1 = f(t, y)` already known, e.g. from FSAL.
+    pub fn stage_derivatives_from<F>(
+        &self,
+        f: &F,
+        t: f64,
+        y: &Array1<f64>,
+        h: f64,
+        k_1: Array1<f64>,
+    ) -> Vec<Array1<f64>>
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let mut k: Vec<Array1<f64>> = Vec::with_capacity(self.stages());
+        k.push(k_1);
+        for (a_i, c_i) in self.a.iter().zip(self.c).skip(1) {
+            let mut y_stage = y.clone();
+            for (a_ij, k_j) in a_i.iter().zip(&k) {
+                if *a_ij != 0. {
+                    y_stage.scaled_add(h * a_ij, k_j);
+                }
+            }
+            k.push(f(t + c_i * h, &y_stage));
+        }
+        k
+    }
+
+    pub fn combine(weights: &[f64], y: &Array1<f64>, h: f64, k: &[Array1<f64>]) -> Array1<f64> {
+        let mut y_next = y.clone();
+        for (w_i, k_i) in weights.iter().zip(k) {
+            if *w_i != 0. {
+                y_next.scaled_add(h * w_i, k_i);
+            }
+        }
+        y_next
+    }
+
+    pub fn step<F>(&self, f: &F, t: f64, y: &Array1<f64>, h: f64) -> Array1<f64>
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let k = self.stage_derivatives(f, t, y, h);
+        self.step_from_stages(y, h, &k)
+    }
+
+    pub fn step_from_stages(&self, y: &Array1<f64>, h: f64, k: &[Array1<f64>]) -> Array1<f64> {
+        Self::combine(self.b, y, h, k)
+    }
+
+    /// Local error estimate `h * sum_i (b_i - b_hat_i) k_i`, if the tableau is embedded.
+    pub fn error_estimate(&self, h: f64, k: &[Array1<f64>]) -> Option<Array1<f64>> {
+        let b_hat = self.b_hat?;
+        let weights: Vec<f64> = self
+            .b
+            .iter()
+            .zip(b_hat)
+            .map(|(b, b_hat)| b - b_hat)
+            .collect();
+        Some(Self::combine(&weights, &Array1::zeros(k[0].len()), h, k))
+    }
+
+    /// First same as last: the final stage is evaluated at `(t + h, y_next)` and can be reused.
+    pub fn is_fsal(&self) -> bool {
+        let s = self.stages();
+        self.c[s - 1] == 1.
+            && self.a[s - 1].iter().zip(self.b).all(|(a, b)| a == b)
+            && self.b[s - 1] == 0.
+    }
+
+    /// Checks the row-sum condition and the order conditions up to `min(order, 4)`,
+    /// for both the propagating and the embedded weights.
+    pub fn satisfies_order_conditions(&self, tol: f64) -> bool {
+        let row_sums =
+            (0..self.stages()).all(|i| (self.a[i].iter().sum::<f64>() - self.c[i]).abs() <= tol);
+        row_sums
+            && self.weights_satisfy_order_conditions(self.b, self.order, tol)
+            && self.b_hat.is_none_or(|b_hat| {
+                self.weights_satisfy_order_conditions(b_hat, self.embedded_order, tol)
+            })
+    }
+
+    fn weights_satisfy_order_conditions(&self, b: &[f64], order: usize, tol: f64) -> bool {
+        let s = self.stages();
+        let (a, c) = (self.a, self.c);
+        let dot = |u: &[f64], v: &[f64]| u.iter().zip(v).map(|(x, y)| x * y).sum::<f64>();
+        let mat_vec = |v: &[f64]| (0..s).map(|i| dot(a[i], &v[..i])).collect::<Vec<f64>>();
+        let close = |x: f64, y: f64| (x - y).abs() <= tol;
+
+        let c2: Vec<f64> = c.iter().map(|x| x * x).collect();
+        let c3: Vec<f64> = c.iter().map(|x| x * x * x).collect();
+        let ac = mat_vec(c);
+        let ac2 = mat_vec(&c2);
+        let aac = mat_vec(&ac);
+        let cac: Vec<f64> = c.iter().zip(&ac).map(|(x, y)| x * y).collect();
+
+        let conditions: [(usize, f64, f64); 8] = [
+            (1, b.iter().sum(), 1.),
+            (2, dot(b, c), 1. / 2.),
+            (3, dot(b, &c2), 1. / 3.),
+            (3, dot(b, &ac), 1. / 6.),
+            (4, dot(b, &c3), 1. / 4.),
+            (4, dot(b, &cac), 1. / 8.),
+            (4, dot(b, &ac2), 1. / 12.),
+            (4, dot(b, &aac), 1. / 24.),
+        ];
+        conditions
+            .iter()
+            .filter(|(condition_order, _, _)| *condition_order <= order)
+            .all(|(_, lhs, rhs)| close(*lhs, *rhs))
+    }
+}
+
+pub const FORWARD_EULER: ButcherTableau<'static> =
+    ButcherTableau::new("Forward Euler", &[&[]], &[1.], &[0.], 1);
+
+pub const EXPLICIT_MIDPOINT: ButcherTableau<'static> = ButcherTableau::new(
+    "Explicit Midpoint",
+    &[&[], &[1. / 2.]],
+    &[0., 1.],
+    &[0., 1. / 2.],
+    2,
+);
+
+pub const HEUN2: ButcherTableau<'static> =
+    ButcherTableau::new("Heun 2", &[&[], &[1.]], &[1. / 2., 1. / 2.], &[0., 1.], 2);
+
+pub const RALSTON2: ButcherTableau<'static> = ButcherTableau::new(
+    "Ralston 2",
+    &[&[], &[2. / 3.]],
+    &[1. / 4., 3. / 4.],
+    &[0., 2. / 3.],
+    2,
+);
+
+pub const KUTTA3: ButcherTableau<'static> = ButcherTableau::new(
+    "Kutta 3",
+    &[&[], &[1. / 2.], &[-1., 2.]],
+    &[1. / 6., 2. / 3., 1. / 6.],
+    &[0., 1. / 2., 1.],
+    3,
+);
+
+pub const WRAY3: ButcherTableau<'static> = ButcherTableau::new(
+    "Wray 3",
+    &[&[], &[8. / 15.], &[1. / 4., 5. / 12.]],
+    &[1. / 4., 0., 3. / 4.],
+    &[0., 8. / 15., 2. / 3.],
+    3,
+);
+
+pub const RALSTON3: ButcherTableau<'static> = ButcherTableau::new(
+    "Ralston 3",
+    &[&[], &[1. / 2.], &[0., 3. / 4.]],
+    &[2. / 9., 1. / 3., 4. / 9.],
+    &[0., 1. / 2., 3. / 4.],
+    3,
+);
+
+pub const SSPRK3: ButcherTableau<'static> = ButcherTableau::new(
+    "SSPRK 3",
+    &[&[], &[1.], &[1. / 4., 1. / 4.]],
+    &[1. / 6., 1. / 6., 2. / 3.],
+    &[0., 1., 1. / 2.],
+    3,
+);
+
+pub const CLASSIC4: ButcherTableau<'static> = ButcherTableau::new(
+    "Classic 4",
+    &[&[], &[1. / 2.], &[0., 1. / 2.], &[0., 0., 1.]],
+    &[1. / 6., 1. / 3., 1. / 3., 1. / 6.],
+    &[0., 1. / 2., 1. / 2., 1.],
+    4,
+);
+
+pub const THREE_8TH: ButcherTableau<'static> = ButcherTableau::new(
+    "3/8 Rule",
+    &[&[], &[1. / 3.], &[-1. / 3., 1.], &[1., -1., 1.]],
+    &[1. / 8., 3. / 8., 3. / 8., 1. / 8.],
+    &[0., 1. / 3., 2. / 3., 1.],
+    4,
+);
+
+pub const BOGACKI_SHAMPINE32: ButcherTableau<'static> = ButcherTableau::embedded(
+    "Bogacki-Shampine 3(2)",
+    &[
+        &[],
+        &[1. / 2.],
+        &[0., 3. / 4.],
+        &[2. / 9., 1. / 3., 4. / 9.],
+    ],
+    &[2. / 9., 1. / 3., 4. / 9., 0.],
+    &[7. / 24., 1. / 4., 1. / 3., 1. / 8.],
+    &[0., 1. / 2., 3. / 4., 1.],
+    3,
+    2,
+);
+
+pub const FEHLBERG45: ButcherTableau<'static> = ButcherTableau::embedded(
+    "Fehlberg 4(5)",
+    &[
+        &[],
+        &[1. / 4.],
+        &[3. / 32., 9. / 32.],
+        &[1932. / 2197., -7200. / 2197., 7296. / 2197.],
+        &[439. / 216., -8., 3680. / 513., -845. / 4104.],
+        &[-8. / 27., 2., -3544. / 2565., 1859. / 4104., -11. / 40.],
+    ],
+    &[25. / 216., 0., 1408. / 2565., 2197. / 4104., -1. / 5., 0.],
+    &[
+        16. / 135.,
+        0.,
+        6656. / 12825.,
+        28561. / 56430.,
+        -9. / 50.,
+        2. / 55.,
+    ],
+    &[0., 1. / 4., 3. / 8., 12. / 13., 1., 1. / 2.],
+    4,
+    5,
+);
+
+pub const CASH_KARP54: ButcherTableau<'static> = ButcherTableau::embedded(
+    "Cash-Karp 5(4)",
+    &[
+        &[],
+        &[1. / 5.],
+        &[3. / 40., 9. / 40.],
+        &[3. / 10., -9. / 10., 6. / 5.],
+        &[-11. / 54., 5. / 2., -70. / 27., 35. / 27.],
+        &[
+            1631. / 55296.,
+            175. / 512.,
+            575. / 13824.,
+            44275. / 110592.,
+            253. / 4096.,
+        ],
+    ],
+    &[37. / 378., 0., 250. / 621., 125. / 594., 0., 512. / 1771.],
+    &[
+        2825. / 27648.,
+        0.,
+        18575. / 48384.,
+        13525. / 55296.,
+        277. / 14336.,
+        1. / 4.,
+    ],
+    &[0., 1. / 5., 3. / 10., 3. / 5., 1., 7. / 8.],
+    5,
+    4,
+);
+
+pub const DORMAND_PRINCE54: ButcherTableau<'static> = ButcherTableau::embedded(
+    "Dormand-Prince 5(4)",
+    &[
+        &[],
+        &[1. / 5.],
+        &[3. / 40., 9. / 40.],
+        &[44. / 45., -56. / 15., 32. / 9.],
+        &[
+            19372. / 6561.,
+            -25360. / 2187.,
+            64448. / 6561.,
+            -212. / 729.,
+        ],
+        &[
+            9017. / 3168.,
+            -355. / 33.,
+            46732. / 5247.,
+            49. / 176.,
+            -5103. / 18656.,
+        ],
+        &[
+            35. / 384.,
+            0.,
+            500. / 1113.,
+            125. / 192.,
+            -2187. / 6784.,
+            11. / 84.,
+        ],
+    ],
+    &[
+        35. / 384.,
+        0.,
+        500. / 1113.,
+        125. / 192.,
+        -2187. / 6784.,
+        11. / 84.,
+        0.,
+    ],
+    &[
+        5179. / 57600.,
+        0.,
+        7571. / 16695.,
+        393. / 640.,
+        -92097. / 339200.,
+        187. / 2100.,
+        1. / 40.,
+    ],
+    &[0., 1. / 5., 3. / 10., 4. / 5., 8. / 9., 1., 1.],
+    5,
+    4,
+)
+.with_dense_output(&[
+    -12715105075. / 11282082432.,
+    0.,
+    87487479700. / 32700410799.,
+    -10690763975. / 1880347072.,
+    701980252875. / 199316789632.,
+    -1453857185. / 822651844.,
+    69997945. / 29380423.,
+]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    const TABLEAUX: [ButcherTableau<'static>; 14] = [
+        FORWARD_EULER,
+        EXPLICIT_MIDPOINT,
+        HEUN2,
+        RALSTON2,
+        KUTTA3,
+        WRAY3,
+        RALSTON3,
+        SSPRK3,
+        CLASSIC4,
+        THREE_8TH,
+        BOGACKI_SHAMPINE32,
+        FEHLBERG45,
+        CASH_KARP54,
+        DORMAND_PRINCE54,
+    ];
+
+    /// Error at `t = 2` of `y' = y cos t`, `y(0) = 1`, whose solution is `exp(sin t)`.
+    fn global_error(tableau: &ButcherTableau, n_steps: usize) -> f64 {
+        let f = |t: f64, y: &Array1<f64>| y * t.cos();
+        let h = 2. / n_steps as f64;
+        let mut y = array![1.];
+        for i in 0..n_steps {
+            y = tableau.step(&f, i as f64 * h, &y, h);
+        }
+        (y[0] - 2f64.sin().exp()).abs()
+    }
+
+    #[test]
+    fn built_in_tableaux_satisfy_their_order_conditions() {
+        for tableau in TABLEAUX {
+            assert!(
+                tableau.satisfies_order_conditions(1e-12),
+                "{}",
+                tableau.name
+            );
+        }
+    }
+
+    #[test]
+    fn perturbed_weights_fail_the_order_conditions() {
+        let b = [1. / 6., 1. / 3., 1. / 3. + 1e-6, 1. / 6. - 1e-6];
+        let tableau = ButcherTableau { b: &b, ..CLASSIC4 };
+        assert!(!tableau.satisfies_order_conditions(1e-12));
+    }
+
+    #[test]
+    fn observed_convergence_order_matches_the_declared_order() {
+        for tableau in &TABLEAUX {
+            let observed = (global_error(tableau, 32) / global_error(tableau, 64)).log2();
+            assert!(
+                (observed - tableau.order as f64).abs() < 0.4,
+                "{}: observed order {}",
+                tableau.name,
+                observed
+            );
+        }
+    }
+
+    #[test]
+    fn custom_tableau_from_run_time_coefficients() {
+        let rows: Vec<Vec<f64>> = vec![vec![], vec![0.5]];
+        let a: Vec<&[f64]> = rows.iter().map(|row| row.as_slice()).collect();
+        let (b, c) = (vec![0., 1.], vec![0., 0.5]);
+        let tableau = ButcherTableau::new("run-time midpoint", &a, &b, &c, 2);
+
+        assert!(tableau.satisfies_order_conditions(1e-12));
+        assert_eq!(
+            global_error(&tableau, 10),
+            global_error(&EXPLICIT_MIDPOINT, 10)
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,161 @@
+use super::{ButcherTableau, DenseSolution, ODESolver};
+use ndarray::Array1;
+
+/// Points `t_0 + sum_j n_j tau_j` with `sum_j n_j <= levels` where the solution of a DDE with a
+/// history that does not join smoothly at `t_0` loses a derivative.
+pub fn breakpoints(t_0: f64, t_end: f64, delays: &[f64], levels: usize) -> Vec<f64> {
+    let mut points = vec![t_0];
+    let mut frontier = vec![t_0];
+    for _ in 0..levels {
+        frontier = frontier
+            .iter()
+            .flat_map(|t| delays.iter().map(move |tau| t + tau))
+            .filter(|t| *t <= t_end)
+            .collect();
+        points.extend(&frontier);
+    }
+    points.sort_by(f64::total_cmp);
+    let tol = 1e-12 * t_end.abs().max(1.);
+    points.dedup_by(|a, b| (*a - *b).abs() <= tol);
+    points
+}
+
+impl ODESolver<'_> {
+    /// Integrates `y'(t) = f(t, y(t), [y(t - tau_1), ..., y(t - tau_m)])` with constant delays,
+    /// taking `y(t) = history(t)` for `t <= t_0`. Steps are at most `h` and never longer than the
+    /// shortest delay, so the lagged values always come from the history or from the dense
+    /// output of steps already taken; they also stop exactly on every breakpoint the initial
+    /// discontinuity propagates to.
+    pub fn solve_dde<F, H>(
+        self,
+        f: F,
+        history: H,
+        delays: &[f64],
+        t_span: (f64, f64),
+        h: f64,
+    ) -> DenseSolution
+    where
+        F: Fn(f64, &Array1<f64>, &[Array1<f64>]) -> Array1<f64>,
+        H: Fn(f64) -> Array1<f64>,
+    {
+        let tableau = self
+            .method
+            .tableau()
+            .expect("delay equations are stepped with an explicit Runge-Kutta method");
+        assert!(
+            delays.iter().all(|tau| *tau > 0.),
+            "delays must be positive"
+        );
+        let (t_0, t_end) = t_span;
+        let tau_min = delays.iter().cloned().fold(f64::INFINITY, f64::min);
+        let h_max = h.min(tau_min);
+
+        let y_0 = history(t_0);
+        let lagged = |solution: &DenseSolution, t: f64| -> Vec<Array1<f64>> {
+            delays
+                .iter()
+                .map(|tau| {
+                    let s = t - tau;
+                    if s <= t_0 {
+                        history(s)
+                    } else {
+                        solution.evaluate(s)
+                    }
+                })
+                .collect()
+        };
+
+        let f_0 = f(
+            t_0,
+            &y_0,
+            &delays
+                .iter()
+                .map(|tau| history(t_0 - tau))
+                .collect::<Vec<_>>(),
+        );
+        let mut solution = DenseSolution::new(t_0, y_0.clone(), f_0);
+
+        let mut points = breakpoints(t_0, t_end, delays, tableau.order + 1)
+            .into_iter()
+            .skip(1);
+        let mut next_breakpoint = points.next().unwrap_or(t_end).min(t_end);
+
+        let mut t = t_0;
+        let mut y = y_0;
+        while t < t_end {
+            let hits_breakpoint = t + h_max >= next_breakpoint;
+            let h = if hits_breakpoint {
+                next_breakpoint - t
+            } else {
+                h_max
+            };
+            let rhs = |s: f64, y: &Array1<f64>| f(s, y, &lagged(&solution, s));
+            let k = tableau.stage_derivatives(&rhs, t, &y, h);
+            let y_next = tableau.step_from_stages(&y, h, &k);
+            let f_next = rhs(t + h, &y_next);
+            let correction = tableau
+                .dense_output
+                .map(|d| ButcherTableau::combine(d, &Array1::zeros(y.len()), h, &k));
+
+            if hits_breakpoint {
+                t = next_breakpoint;
+                next_breakpoint = points.next().unwrap_or(t_end).min(t_end);
+            } else {
+                t += h;
+            }
+            solution.push(t, y_next.clone(), f_next, correction);
+            y = y_next;
+        }
+        solution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+
+    #[test]
+    fn breakpoints_combine_the_delays() {
+        assert_eq!(
+            breakpoints(0., 3., &[1., 1.5], 2),
+            vec![0., 1., 1.5, 2., 2.5, 3.]
+        );
+    }
+
+    #[test]
+    fn method_of_steps_solution_of_a_pure_delay() {
+        // y'(t) = -y(t - 1) with y = 1 for t <= 0 is 1 - t on [0, 1] and
+        // 1 - t + (t - 1)^2 / 2 on [1, 2]
+        let solution = ODESolver::new(SolverMethod::Classic4).solve_dde(
+            |_, _, lagged| -&lagged[0],
+            |_| array![1.],
+            &[1.],
+            (0., 2.),
+            0.1,
+        );
+        for t in [0.5, 1., 1.5, 2.] {
+            let exact = if t <= 1. {
+                1. - t
+            } else {
+                1. - t + (t - 1.) * (t - 1.) / 2.
+            };
+            assert!((solution.evaluate(t)[0] - exact).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn hutchinson_settles_on_the_carrying_capacity() {
+        // r tau = 1 < pi / 2, so the equilibrium n = k is stable
+        let (r, k, tau) = (1., 10., 1.);
+        let solution = ODESolver::new(SolverMethod::DormandPrince54).solve_dde(
+            |_, n, lagged| r * n * (1. - &lagged[0] / k),
+            |_| array![2.],
+            &[tau],
+            (0., 60.),
+            0.05,
+        );
+        assert!((solution.evaluate(60.)[0] - k).abs() < 1e-4);
+    }
+}
\ No newline at end of file
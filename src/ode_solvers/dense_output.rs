@@ -0,0 +1,256 @@
+use super::events::{Event, EventRecord, RootFinder};
+use super::{AdaptiveOptions, ButcherTableau, ODESolver, StepSizeUnderflow, Stepper};
+use crate::linalg;
+use ndarray::{Array1, Array2, Axis};
+
+/// A solution that can be evaluated at any time in its span. Each step carries the cubic
+/// Hermite interpolant through its end points and slopes, plus a correction term
+/// `theta^2 (1 - theta)^2 r` when the method has its own continuous extension.
+pub struct DenseSolution {
+    t: Vec<f64>,
+    y: Vec<Array1<f64>>,
+    f: Vec<Array1<f64>>,
+    corrections: Vec<Option<Array1<f64>>>,
+}
+
+impl DenseSolution {
+    pub fn new(t_0: f64, y_0: Array1<f64>, f_0: Array1<f64>) -> Self {
+        Self {
+            t: vec![t_0],
+            y: vec![y_0],
+            f: vec![f_0],
+            corrections: Vec::new(),
+        }
+    }
+
+    /// Appends a step ending at `(t, y)` with slope `f` there.
+    pub fn push(
+        &mut self,
+        t: f64,
+        y: Array1<f64>,
+        f: Array1<f64>,
+        correction: Option<Array1<f64>>,
+    ) {
+        assert!(
+            t > *self.t.last().unwrap(),
+            "steps must move forward in time"
+        );
+        self.t.push(t);
+        self.y.push(y);
+        self.f.push(f);
+        self.corrections.push(correction);
+    }
+
+    pub fn t_span(&self) -> (f64, f64) {
+        (self.t[0], *self.t.last().unwrap())
+    }
+
+    pub fn nodes(&self) -> Array1<f64> {
+        Array1::from(self.t.clone())
+    }
+
+    pub fn states(&self) -> Array2<f64> {
+        let views: Vec<_> = self.y.iter().map(|y| y.view()).collect();
+        ndarray::stack(Axis(0), &views).unwrap()
+    }
+
+    fn segment(&self, t: f64) -> usize {
+        let (t_0, t_end) = self.t_span();
+        let tol = 1e-12 * t_end.abs().max(t_0.abs()).max(1.);
+        assert!(
+            t >= t_0 - tol && t <= t_end + tol,
+            "t = {} is outside the solution span [{}, {}]",
+            t,
+            t_0,
+            t_end
+        );
+        self.t
+            .partition_point(|t_i| *t_i <= t)
+            .clamp(1, self.t.len() - 1)
+            - 1
+    }
+
+    pub fn evaluate(&self, t: f64) -> Array1<f64> {
+        let i = self.segment(t);
+        let h = self.t[i + 1] - self.t[i];
+        let theta = (t - self.t[i]) / h;
+        let theta1 = 1. - theta;
+
+        let dy = &self.y[i + 1] - &self.y[i];
+        let r3 = h * &self.f[i] - &dy;
+        let r4 = &dy - &(h * &self.f[i + 1]) - &r3;
+        let mut inner = r4;
+        if let Some(r5) = &self.corrections[i] {
+            inner.scaled_add(theta1, r5);
+        }
+        let inner = r3 + theta * inner;
+        let inner = dy + theta1 * inner;
+        &self.y[i] + &(theta * inner)
+    }
+
+    /// Evaluates the solution on a new grid, one row per time point.
+    pub fn resample(&self, t: &Array1<f64>) -> Array2<f64> {
+        let mut y = Array2::zeros((t.len(), self.y[0].len()));
+        for (mut row, t_i) in y.rows_mut().into_iter().zip(t) {
+            row.assign(&self.evaluate(*t_i));
+        }
+        y
+    }
+
+    /// Locates the zero crossings of `events` on the interpolant without rerunning the solver.
+    /// Stops at the first terminal event.
+    pub fn find_events(&self, events: &[Event], root_finder: RootFinder) -> Vec<EventRecord> {
+        let mut records = Vec::new();
+        let mut g_prev: Vec<f64> = events
+            .iter()
+            .map(|e| e.value(self.t[0], &self.y[0]))
+            .collect();
+        for i in 0..self.t.len() - 1 {
+            let (t_a, t_b) = (self.t[i], self.t[i + 1]);
+            let g_next: Vec<f64> = events
+                .iter()
+                .map(|e| e.value(t_b, &self.y[i + 1]))
+                .collect();
+            let mut found: Vec<EventRecord> = events
+                .iter()
+                .enumerate()
+                .filter(|(k, event)| event.direction.crosses(g_prev[*k], g_next[*k]))
+                .map(|(k, event)| {
+                    let t_event = root_finder.find_root(
+                        |tau| event.value(tau, &self.evaluate(tau)),
+                        (t_a, g_prev[k]),
+                        (t_b, g_next[k]),
+                    );
+                    EventRecord {
+                        event: k,
+                        t: t_event,
+                        y: self.evaluate(t_event),
+                    }
+                })
+                .collect();
+            found.sort_by(|a, b| a.t.total_cmp(&b.t));
+
+            if let Some(stop) = found.iter().position(|r| events[r.event].terminal) {
+                found.truncate(stop + 1);
+                records.extend(found);
+                break;
+            }
+            records.extend(found);
+            g_prev = g_next;
+        }
+        records
+    }
+}
+
+fn continuous_extension(
+    tableau: &ButcherTableau,
+    h: f64,
+    k: &[Array1<f64>],
+) -> Option<Array1<f64>> {
+    tableau
+        .dense_output
+        .map(|d| ButcherTableau::combine(d, &Array1::zeros(k[0].len()), h, k))
+}
+
+impl ODESolver<'_> {
+    /// Fixed-step `solve` that keeps the slopes at every step for Hermite interpolation.
+    pub fn solve_dense<F>(
+        self,
+        f: F,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+    ) -> DenseSolution
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let jac = |t: f64, y: &Array1<f64>| linalg::jacobian(|y| f(t, y), y);
+        let mut stepper = Stepper::new(self, &f, &jac, h, t[0], y_0);
+        let mut solution = DenseSolution::new(t[0], y_0.clone(), f(t[0], y_0));
+        for i in 0..n_steps {
+            let y_next = stepper.step(t[i]);
+            let f_next = f(t[i] + h, &y_next);
+            solution.push(t[i] + h, y_next, f_next, None);
+        }
+        solution
+    }
+
+    /// `solve_adaptive` returning a continuous solution, using the method's own continuous
+    /// extension when the tableau has one (Dormand-Prince) and Hermite interpolation otherwise.
+    pub fn solve_adaptive_dense<F>(
+        self,
+        f: F,
+        t_span: (f64, f64),
+        y_0: &Array1<f64>,
+        options: &AdaptiveOptions,
+    ) -> Result<DenseSolution, StepSizeUnderflow>
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let tableau = self.method.tableau();
+        let mut solution = DenseSolution::new(t_span.0, y_0.clone(), f(t_span.0, y_0));
+        self.integrate_adaptive(&f, t_span, y_0, options, |step| {
+            let correction =
+                tableau.and_then(|tableau| continuous_extension(&tableau, step.h, step.k));
+            solution.push(
+                step.t + step.h,
+                step.y_next.clone(),
+                step.f_next.clone(),
+                correction,
+            );
+        })?;
+        Ok(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+
+    /// Largest error at the step midpoints of the Hermite interpolant through exact samples of
+    /// `e^t` on `[0, 1]`.
+    fn hermite_midpoint_error(n_steps: usize) -> f64 {
+        let h = 1. / n_steps as f64;
+        let mut solution = DenseSolution::new(0., array![1.], array![1.]);
+        for i in 1..=n_steps {
+            let t = i as f64 * h;
+            solution.push(t, array![t.exp()], array![t.exp()], None);
+        }
+        (0..n_steps)
+            .map(|i| {
+                let t = (i as f64 + 0.5) * h;
+                (solution.evaluate(t)[0] - t.exp()).abs()
+            })
+            .fold(0., f64::max)
+    }
+
+    #[test]
+    fn hermite_interpolant_is_fourth_order() {
+        let ratio = hermite_midpoint_error(10) / hermite_midpoint_error(20);
+        assert!((ratio - 16.).abs() < 1., "error ratio {}", ratio);
+    }
+
+    #[test]
+    fn dormand_prince_extension_is_accurate_between_steps() {
+        let solution = ODESolver::new(SolverMethod::DormandPrince54)
+            .solve_adaptive_dense(
+                |_, y| -y,
+                (0., 5.),
+                &array![1.],
+                &AdaptiveOptions::default(),
+            )
+            .unwrap();
+        let t = Array1::linspace(0., 5., 501);
+        let error = solution
+            .resample(&t)
+            .column(0)
+            .iter()
+            .zip(&t)
+            .map(|(y, t)| (y - (-t).exp()).abs())
+            .fold(0., f64::max);
+        assert!(error < 1e-6, "{:e}", error);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,241 @@
+use super::{DenseSolution, ODESolver, Stepper};
+use crate::linalg;
+use ndarray::{Array1, Array2, Axis};
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum EventDirection {
+    Rising,
+    Falling,
+    Either,
+}
+
+impl EventDirection {
+    pub fn crosses(self, g_a: f64, g_b: f64) -> bool {
+        let rising = g_a < 0. && g_b >= 0.;
+        let falling = g_a > 0. && g_b <= 0.;
+        match self {
+            EventDirection::Rising => rising,
+            EventDirection::Falling => falling,
+            EventDirection::Either => rising || falling,
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum RootFinder {
+    Bisection,
+    Illinois,
+}
+
+impl RootFinder {
+    /// Finds a root of `g` in `[a, b]`, given `g(a)` and `g(b)` of opposite sign.
+    pub fn find_root<G>(
+        self,
+        g: G,
+        (mut a, mut g_a): (f64, f64),
+        (mut b, mut g_b): (f64, f64),
+    ) -> f64
+    where
+        G: Fn(f64) -> f64,
+    {
+        let tol = 4. * f64::EPSILON * a.abs().max(b.abs()).max(1.);
+        let illinois = matches!(self, RootFinder::Illinois);
+        let mut side = 0;
+        for _ in 0..200 {
+            if (b - a).abs() <= tol {
+                break;
+            }
+            let m = match self {
+                RootFinder::Bisection => (a + b) / 2.,
+                RootFinder::Illinois => (a * g_b - b * g_a) / (g_b - g_a),
+            };
+            let g_m = g(m);
+            if g_m == 0. {
+                return m;
+            }
+            if (g_m < 0.) == (g_a < 0.) {
+                a = m;
+                g_a = g_m;
+                // the retained endpoint `b` has not moved twice in a row, halve its weight
+                if illinois && side == -1 {
+                    g_b /= 2.;
+                }
+                side = -1;
+            } else {
+                b = m;
+                g_b = g_m;
+                if illinois && side == 1 {
+                    g_a /= 2.;
+                }
+                side = 1;
+            }
+        }
+        if g_a.abs() < g_b.abs() {
+            a
+        } else {
+            b
+        }
+    }
+}
+
+type EventFunction<'a> = Box<dyn Fn(f64, &Array1<f64>) -> f64 + 'a>;
+
+/// A function `g(t, y)` whose zero crossings in `direction` are located during integration.
+/// A terminal event stops the integration where it occurs.
+pub struct Event<'a> {
+    g: EventFunction<'a>,
+    pub direction: EventDirection,
+    pub terminal: bool,
+}
+
+impl<'a> Event<'a> {
+    pub fn new<G>(g: G, direction: EventDirection, terminal: bool) -> Self
+    where
+        G: Fn(f64, &Array1<f64>) -> f64 + 'a,
+    {
+        Self {
+            g: Box::new(g),
+            direction,
+            terminal,
+        }
+    }
+
+    pub fn value(&self, t: f64, y: &Array1<f64>) -> f64 {
+        (self.g)(t, y)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct EventRecord {
+    pub event: usize,
+    pub t: f64,
+    pub y: Array1<f64>,
+}
+
+impl ODESolver<'_> {
+    /// Integrates like `solve`, locating the zero crossings of `events` inside each step on the
+    /// cubic Hermite interpolant through the step's end points and slopes, so the event times
+    /// come from the steps the method actually took. Returns the time points, the states there
+    /// and the events in order of occurrence; after a terminal event the trajectory ends at the
+    /// event.
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve_with_events<F>(
+        self,
+        f: F,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+        events: &[Event],
+        root_finder: RootFinder,
+    ) -> (Array1<f64>, Array2<f64>, Vec<EventRecord>)
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let jac = |t: f64, y: &Array1<f64>| linalg::jacobian(|y| f(t, y), y);
+        let mut stepper = Stepper::new(self, &f, &jac, h, t[0], y_0);
+
+        let mut ts = vec![t[0]];
+        let mut ys = vec![y_0.clone()];
+        let mut records = Vec::new();
+        let mut g_prev: Vec<f64> = events.iter().map(|e| e.value(t[0], y_0)).collect();
+        let mut f_prev = f(t[0], y_0);
+
+        for i in 0..n_steps {
+            let y_i = stepper.y().clone();
+            let y_next = stepper.step(t[i]);
+            let t_next = t[i] + h;
+            let f_next = f(t_next, &y_next);
+            let g_next: Vec<f64> = events.iter().map(|e| e.value(t_next, &y_next)).collect();
+
+            let crossed: Vec<usize> = (0..events.len())
+                .filter(|k| events[*k].direction.crosses(g_prev[*k], g_next[*k]))
+                .collect();
+            let mut found: Vec<EventRecord> = Vec::new();
+            if !crossed.is_empty() {
+                let mut step = DenseSolution::new(t[i], y_i, f_prev.clone());
+                step.push(t_next, y_next.clone(), f_next.clone(), None);
+                found = crossed
+                    .into_iter()
+                    .map(|k| {
+                        let event = &events[k];
+                        let t_event = root_finder.find_root(
+                            |tau| event.value(tau, &step.evaluate(tau)),
+                            (t[i], g_prev[k]),
+                            (t_next, g_next[k]),
+                        );
+                        EventRecord {
+                            event: k,
+                            t: t_event,
+                            y: step.evaluate(t_event),
+                        }
+                    })
+                    .collect();
+                found.sort_by(|a, b| a.t.total_cmp(&b.t));
+            }
+
+            if let Some(stop) = found.iter().position(|r| events[r.event].terminal) {
+                found.truncate(stop + 1);
+                ts.push(found[stop].t);
+                ys.push(found[stop].y.clone());
+                records.extend(found);
+                break;
+            }
+            records.extend(found);
+            ts.push(t_next);
+            ys.push(y_next);
+            g_prev = g_next;
+            f_prev = f_next;
+        }
+
+        let views: Vec<_> = ys.iter().map(|y| y.view()).collect();
+        (
+            Array1::from(ts),
+            ndarray::stack(Axis(0), &views).unwrap(),
+            records,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+
+    #[test]
+    fn root_finders_agree_on_a_cube_root() {
+        let g = |x: f64| x * x * x - 2.;
+        for root_finder in [RootFinder::Bisection, RootFinder::Illinois] {
+            let root = root_finder.find_root(g, (0., g(0.)), (2., g(2.)));
+            assert!((root - 2_f64.cbrt()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn terminal_event_stops_at_a_linear_crossing() {
+        // y' = 2 from y(0) = 0 reaches 0.74 at t = 0.37, inside the fourth step
+        let (h, n_steps) = (0.1, 10);
+        let t = Array1::linspace(0., 1., n_steps + 1);
+        let events = [Event::new(
+            |_, y: &Array1<f64>| y[0] - 0.74,
+            EventDirection::Rising,
+            true,
+        )];
+        for root_finder in [RootFinder::Bisection, RootFinder::Illinois] {
+            let (ts, ys, records) = ODESolver::new(SolverMethod::Classic4).solve_with_events(
+                |_, y| Array1::from_elem(y.len(), 2.),
+                h,
+                n_steps,
+                &t,
+                &array![0.],
+                &events,
+                root_finder,
+            );
+            assert_eq!(records.len(), 1);
+            assert!((records[0].t - 0.37).abs() < 1e-12);
+            assert!((ts[ts.len() - 1] - 0.37).abs() < 1e-12);
+            assert!((ys[[ys.nrows() - 1, 0]] - 0.74).abs() < 1e-12);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,273 @@
+use crate::linalg::LuDecomposition;
+use ndarray::{s, Array1, Array2};
+
+const NEWTON_TOL: f64 = 1e-10;
+const NEWTON_MAX_ITERATIONS: usize = 50;
+
+/// `y_{n+1} = sum_j alpha_j y_{n-j} + h * beta * f(t_{n+1}, y_{n+1})` for BDF orders 1 to 5.
+pub(super) const BDF_COEFFICIENTS: [(&[f64], f64); 5] = [
+    (&[1.], 1.),
+    (&[4. / 3., -1. / 3.], 2. / 3.),
+    (&[18. / 11., -9. / 11., 2. / 11.], 6. / 11.),
+    (&[48. / 25., -36. / 25., 16. / 25., -3. / 25.], 12. / 25.),
+    (
+        &[
+            300. / 137.,
+            -300. / 137.,
+            200. / 137.,
+            -75. / 137.,
+            12. / 137.,
+        ],
+        60. / 137.,
+    ),
+];
+
+fn newton_converged(delta: &Array1<f64>, z: &Array1<f64>) -> bool {
+    let max_abs = |v: &Array1<f64>| v.iter().fold(0., |m: f64, x| m.max(x.abs()));
+    max_abs(delta) <= NEWTON_TOL * (1. + max_abs(z))
+}
+
+/// Solves `z = psi + h_gamma * f(t, z)` by simplified Newton with the Jacobian frozen at `guess`.
+/// `None` if the iteration matrix is singular or Newton does not converge, so the caller can cut
+/// the step.
+pub fn solve_implicit_equation<F, J>(
+    f: &F,
+    jac: &J,
+    t: f64,
+    psi: &Array1<f64>,
+    h_gamma: f64,
+    guess: Array1<f64>,
+) -> Option<Array1<f64>>
+where
+    F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    J: Fn(f64, &Array1<f64>) -> Array2<f64>,
+{
+    let n = psi.len();
+    let w = Array2::eye(n) - h_gamma * jac(t, &guess);
+    let lu = LuDecomposition::new(&w)?;
+
+    let mut z = guess;
+    for _ in 0..NEWTON_MAX_ITERATIONS {
+        let residual = &z - psi - h_gamma * f(t, &z);
+        let delta = lu.solve(&residual);
+        z -= &delta;
+        if !z.iter().all(|z_i| z_i.is_finite()) {
+            return None;
+        }
+        if newton_converged(&delta, &z) {
+            return Some(z);
+        }
+    }
+    None
+}
+
+/// Coefficient matrix `A` and nodes `c` of the stiffly accurate Radau IIA methods.
+fn radau_iia_coefficients(stages: usize) -> (Array2<f64>, Array1<f64>) {
+    match stages {
+        2 => (
+            ndarray::arr2(&[[5. / 12., -1. / 12.], [3. / 4., 1. / 4.]]),
+            ndarray::arr1(&[1. / 3., 1.]),
+        ),
+        3 => {
+            let sq6 = 6f64.sqrt();
+            (
+                ndarray::arr2(&[
+                    [
+                        (88. - 7. * sq6) / 360.,
+                        (296. - 169. * sq6) / 1800.,
+                        (-2. + 3. * sq6) / 225.,
+                    ],
+                    [
+                        (296. + 169. * sq6) / 1800.,
+                        (88. + 7. * sq6) / 360.,
+                        (-2. - 3. * sq6) / 225.,
+                    ],
+                    [(16. - sq6) / 36., (16. + sq6) / 36., 1. / 9.],
+                ]),
+                ndarray::arr1(&[(4. - sq6) / 10., (4. + sq6) / 10., 1.]),
+            )
+        }
+        _ => panic!("Radau IIA is only provided with 2 or 3 stages"),
+    }
+}
+
+pub(super) fn radau_iia_step<F, J>(
+    f: &F,
+    jac: &J,
+    t: f64,
+    y: &Array1<f64>,
+    h: f64,
+    stages: usize,
+) -> Option<Array1<f64>>
+where
+    F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    J: Fn(f64, &Array1<f64>) -> Array2<f64>,
+{
+    let (a, c) = radau_iia_coefficients(stages);
+    let n = y.len();
+    let jac_y = jac(t, y);
+
+    let mut w = Array2::eye(stages * n);
+    for i in 0..stages {
+        for j in 0..stages {
+            let mut block = w.slice_mut(s![i * n..(i + 1) * n, j * n..(j + 1) * n]);
+            block.scaled_add(-h * a[[i, j]], &jac_y);
+        }
+    }
+    let lu = LuDecomposition::new(&w)?;
+
+    let mut z = Array1::zeros(stages * n);
+    for _ in 0..NEWTON_MAX_ITERATIONS {
+        let f_z: Vec<Array1<f64>> = (0..stages)
+            .map(|j| f(t + c[j] * h, &(y + &z.slice(s![j * n..(j + 1) * n]))))
+            .collect();
+        let mut residual = z.clone();
+        for i in 0..stages {
+            let mut residual_i = residual.slice_mut(s![i * n..(i + 1) * n]);
+            for (j, f_j) in f_z.iter().enumerate() {
+                residual_i.scaled_add(-h * a[[i, j]], f_j);
+            }
+        }
+        let delta = lu.solve(&residual);
+        z -= &delta;
+        if !z.iter().all(|z_i| z_i.is_finite()) {
+            return None;
+        }
+        if newton_converged(&delta, &z) {
+            return Some(y + &z.slice(s![(stages - 1) * n..]));
+        }
+    }
+    None
+}
+
+/// Rosenbrock ROS2 of Verwer et al., L-stable and second order, one LU per step. `None` if the
+/// matrix is singular or the step is not finite.
+pub(super) fn rosenbrock2_step<F, J>(
+    f: &F,
+    jac: &J,
+    t: f64,
+    y: &Array1<f64>,
+    h: f64,
+) -> Option<Array1<f64>>
+where
+    F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    J: Fn(f64, &Array1<f64>) -> Array2<f64>,
+{
+    let gamma = 1. + 1. / 2f64.sqrt();
+    let f_y = f(t, y);
+    let dt = f64::EPSILON.sqrt() * t.abs().max(1.);
+    let f_t = (f(t + dt, y) - &f_y) / dt;
+
+    let w = Array2::eye(y.len()) - gamma * h * jac(t, y);
+    let lu = LuDecomposition::new(&w)?;
+
+    let k1 = lu.solve(&(&f_y + &(gamma * h * &f_t)));
+    let k2 = lu.solve(&(f(t + h, &(y + &(h * &k1))) - 2. * &k1 - gamma * h * &f_t));
+    let y_next = y + &(h * (1.5 * &k1 + 0.5 * &k2));
+    y_next.iter().all(|y_i| y_i.is_finite()).then_some(y_next)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ode_solvers::{ODESolver, SolverMethod};
+    use ndarray::{array, Array1};
+
+    const L_STABLE: [SolverMethod; 9] = [
+        SolverMethod::BackwardEuler,
+        SolverMethod::BDF(1),
+        SolverMethod::BDF(2),
+        SolverMethod::BDF(3),
+        SolverMethod::BDF(4),
+        SolverMethod::BDF(5),
+        SolverMethod::RadauIIA3,
+        SolverMethod::RadauIIA5,
+        SolverMethod::Rosenbrock2,
+    ];
+
+    /// Error at `t = 1` of `y' = -(y - cos t)` from `y(0) = 0` in `n` steps, against
+    /// `(cos t + sin t - e^-t) / 2`.
+    fn error(method: SolverMethod, n: usize) -> f64 {
+        let t = Array1::linspace(0., 1., n + 1);
+        let y =
+            ODESolver::new(method).solve(|t, y| -(y - t.cos()), 1. / n as f64, n, &t, &array![0.]);
+        let exact = (1f64.cos() + 1f64.sin() - (-1f64).exp()) / 2.;
+        (y[[n, 0]] - exact).abs()
+    }
+
+    #[test]
+    fn implicit_methods_converge_at_their_order() {
+        let methods = [
+            (SolverMethod::BackwardEuler, 1.),
+            (SolverMethod::Trapezoidal, 2.),
+            (SolverMethod::ImplicitMidpoint, 2.),
+            (SolverMethod::BDF(2), 2.),
+            (SolverMethod::BDF(3), 3.),
+            (SolverMethod::BDF(4), 4.),
+            (SolverMethod::RadauIIA3, 3.),
+            (SolverMethod::RadauIIA5, 5.),
+            (SolverMethod::Rosenbrock2, 2.),
+        ];
+        for (method, order) in methods {
+            let observed = (error(method, 20) / error(method, 40)).log2();
+            assert!(
+                (observed - order).abs() < 0.3,
+                "observed order {} for order {}",
+                observed,
+                order
+            );
+        }
+    }
+
+    #[test]
+    fn stiff_decay_is_damped_with_large_steps() {
+        // Dahlquist's y' = lambda y with h lambda = -1e5, where explicit methods blow up
+        let (lambda, h, n) = (-1e6, 0.1, 20);
+        let t = Array1::linspace(0., h * n as f64, n + 1);
+        for method in L_STABLE {
+            let y = ODESolver::new(method).solve(|_, y| lambda * y, h, n, &t, &array![1.]);
+            assert!(y[[n, 0]].abs() < 1e-6, "{}", y[[n, 0]]);
+        }
+        for method in [SolverMethod::Trapezoidal, SolverMethod::ImplicitMidpoint] {
+            // A-stable but not L-stable: bounded, not damped
+            let y = ODESolver::new(method).solve(|_, y| lambda * y, h, n, &t, &array![1.]);
+            assert!(y.iter().all(|y| y.abs() <= 1. + 1e-9));
+        }
+    }
+
+    #[test]
+    fn robertson_matches_the_reference_solution() {
+        // Robertson's chemical kinetics, stiff with rates from 0.04 to 3e7, at t = 40
+        let robertson = |_: f64, y: &Array1<f64>| {
+            array![
+                -0.04 * y[0] + 1e4 * y[1] * y[2],
+                0.04 * y[0] - 1e4 * y[1] * y[2] - 3e7 * y[1] * y[1],
+                3e7 * y[1] * y[1]
+            ]
+        };
+        let (h, n) = (0.01, 4000);
+        let t = Array1::linspace(0., 40., n + 1);
+        for method in [SolverMethod::RadauIIA5, SolverMethod::BDF(2)] {
+            let y = ODESolver::new(method).solve(robertson, h, n, &t, &array![1., 0., 0.]);
+            let reference = [0.7158270687, 9.185534764e-6, 0.2841637457];
+            for (j, y_ref) in reference.into_iter().enumerate() {
+                assert!(((y[[n, j]] - y_ref) / y_ref).abs() < 1e-3, "{}", y.row(n));
+            }
+            assert!((y.row(n).sum() - 1.).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn a_failed_newton_iteration_cuts_the_step() {
+        // backward Euler on y' = y with h = 1 has the singular iteration matrix 1 - h, so the step
+        // is taken as two of 1/2, each multiplying y by 2
+        let t = array![0., 1.];
+        let y = ODESolver::new(SolverMethod::BackwardEuler).solve(
+            |_, y| y.clone(),
+            1.,
+            1,
+            &t,
+            &array![1.],
+        );
+        assert!((y[[1, 0]] - 4.).abs() < 1e-9);
+    }
+}
\ No newline at end of file
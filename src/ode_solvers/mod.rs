@@ -0,0 +1,367 @@
+mod adaptive;
+pub mod butcher_tableau;
+mod dde;
+mod dense_output;
+mod events;
+mod implicit;
+pub mod multistep;
+mod positivity;
+mod rhs;
+mod sensitivity;
+mod stepper;
+mod symplectic;
+
+pub use adaptive::{AdaptiveOptions, StepSizeUnderflow};
+pub use butcher_tableau::ButcherTableau;
+pub use dde::breakpoints;
+pub use dense_output::DenseSolution;
+pub use events::{Event, EventDirection, EventRecord, RootFinder};
+pub use implicit::solve_implicit_equation;
+pub use multistep::LinearMultistep;
+pub use positivity::{PatankarMethod, PatankarSolver};
+pub use rhs::RightHandSide;
+pub use sensitivity::Sensitivities;
+pub use stepper::Stepper;
+pub use symplectic::{
+    first_integral_drift, lotka_volterra_first_integral, PartitionedSolver, SymplecticMethod,
+};
+
+use crate::linalg;
+use ndarray::{array, Array1, Array2};
+
+#[derive(Copy, Clone)]
+pub enum SolverMethod<'a> {
+    ForwardEuler,
+    ExplicitMidpoint,
+    Heun2,
+    Ralston2,
+    Kutta3,
+    Wray3,
+    Ralston3,
+    SSPRK3,
+    Classic4,
+    Three8th,
+    BogackiShampine32,
+    Fehlberg45,
+    CashKarp54,
+    DormandPrince54,
+    Custom(ButcherTableau<'a>),
+    BackwardEuler,
+    Trapezoidal,
+    ImplicitMidpoint,
+    BDF(usize),
+    RadauIIA3,
+    RadauIIA5,
+    Rosenbrock2,
+    AdamsBashforth(usize),
+    AdamsMoulton(usize),
+    Nystrom2,
+    Nystrom3,
+    MilneSimpson4,
+}
+
+impl<'a> SolverMethod<'a> {
+    /// The explicit Runge-Kutta tableau, or `None` for the implicit and multistep methods.
+    pub fn tableau(self) -> Option<ButcherTableau<'a>> {
+        let tableau = match self {
+            SolverMethod::ForwardEuler => butcher_tableau::FORWARD_EULER,
+            SolverMethod::ExplicitMidpoint => butcher_tableau::EXPLICIT_MIDPOINT,
+            SolverMethod::Heun2 => butcher_tableau::HEUN2,
+            SolverMethod::Ralston2 => butcher_tableau::RALSTON2,
+            SolverMethod::Kutta3 => butcher_tableau::KUTTA3,
+            SolverMethod::Wray3 => butcher_tableau::WRAY3,
+            SolverMethod::Ralston3 => butcher_tableau::RALSTON3,
+            SolverMethod::SSPRK3 => butcher_tableau::SSPRK3,
+            SolverMethod::Classic4 => butcher_tableau::CLASSIC4,
+            SolverMethod::Three8th => butcher_tableau::THREE_8TH,
+            SolverMethod::BogackiShampine32 => butcher_tableau::BOGACKI_SHAMPINE32,
+            SolverMethod::Fehlberg45 => butcher_tableau::FEHLBERG45,
+            SolverMethod::CashKarp54 => butcher_tableau::CASH_KARP54,
+            SolverMethod::DormandPrince54 => butcher_tableau::DORMAND_PRINCE54,
+            SolverMethod::Custom(tableau) => tableau,
+            SolverMethod::BackwardEuler
+            | SolverMethod::Trapezoidal
+            | SolverMethod::ImplicitMidpoint
+            | SolverMethod::BDF(_)
+            | SolverMethod::RadauIIA3
+            | SolverMethod::RadauIIA5
+            | SolverMethod::Rosenbrock2
+            | SolverMethod::AdamsBashforth(_)
+            | SolverMethod::AdamsMoulton(_)
+            | SolverMethod::Nystrom2
+            | SolverMethod::Nystrom3
+            | SolverMethod::MilneSimpson4 => return None,
+        };
+        Some(tableau)
+    }
+
+    pub fn is_implicit(self) -> bool {
+        matches!(
+            self,
+            SolverMethod::BackwardEuler
+                | SolverMethod::Trapezoidal
+                | SolverMethod::ImplicitMidpoint
+                | SolverMethod::BDF(_)
+                | SolverMethod::RadauIIA3
+                | SolverMethod::RadauIIA5
+                | SolverMethod::Rosenbrock2
+        )
+    }
+
+    /// Rejects orders the BDF and Adams families are not provided for, when the solver is built
+    /// rather than once the startup steps are done.
+    fn check_order(self) {
+        match self {
+            SolverMethod::BDF(order) => {
+                assert!((1..=5).contains(&order), "BDF is only stable up to order 5")
+            }
+            SolverMethod::AdamsBashforth(_) | SolverMethod::AdamsMoulton(_) => {
+                self.multistep();
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ODESolver<'a> {
+    method: SolverMethod<'a>,
+    starter: ButcherTableau<'a>,
+    positive: bool,
+}
+
+impl<'a> ODESolver<'a> {
+    pub fn new(method: SolverMethod<'a>) -> Self {
+        method.check_order();
+        Self {
+            method,
+            starter: butcher_tableau::CLASSIC4,
+            positive: false,
+        }
+    }
+
+    /// Keeps components that start non-negative from going below zero. A step that would cross
+    /// zero is retried as two half steps, each halved again while it still crosses, up to ten
+    /// times. Whatever still crosses zero after that is clamped to zero, so the states stay
+    /// non-negative but those components are no longer the method's solution. Adaptive runs
+    /// reject such steps instead.
+    pub fn with_positivity(mut self) -> Self {
+        self.positive = true;
+        self
+    }
+
+    /// Sets the one-step method used to fill the history of the multistep methods. Steps taken
+    /// outside that history also use it: the halved substeps of `with_positivity` and every
+    /// `Stepper::one_step`, which the Lyapunov exponent estimates advance by.
+    pub fn with_starter(mut self, starter: SolverMethod<'a>) -> Self {
+        self.starter = starter
+            .tableau()
+            .expect("the starter must be an explicit Runge-Kutta method");
+        self
+    }
+
+    pub fn solve<F>(
+        self,
+        f: F,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+    ) -> Array2<f64>
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let jac = |t: f64, y: &Array1<f64>| linalg::jacobian(|y| f(t, y), y);
+        self.solve_with_jacobian(&f, jac, h, n_steps, t, y_0)
+    }
+
+    /// Like `solve`, with an analytic Jacobian `df/dy` for the implicit methods' Newton
+    /// iterations instead of finite differences. Explicit methods ignore it.
+    pub fn solve_with_jacobian<F, J>(
+        self,
+        f: F,
+        jac: J,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+    ) -> Array2<f64>
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        J: Fn(f64, &Array1<f64>) -> Array2<f64>,
+    {
+        let mut stepper = Stepper::new(self, &f, &jac, h, t[0], y_0);
+        let mut y = Array2::zeros((n_steps + 1, y_0.len()));
+        y.row_mut(0).assign(y_0);
+        for i in 0..n_steps {
+            y.row_mut(i + 1).assign(&stepper.step(t[i]));
+        }
+        y
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ODESolver1<'a> {
+    method: SolverMethod<'a>,
+    positive: bool,
+}
+
+impl<'a> ODESolver1<'a> {
+    pub fn new(method: SolverMethod<'a>) -> Self {
+        method.check_order();
+        Self {
+            method,
+            positive: false,
+        }
+    }
+
+    /// See `ODESolver::with_positivity`.
+    pub fn with_positivity(mut self) -> Self {
+        self.positive = true;
+        self
+    }
+
+    fn ode_solver(self) -> ODESolver<'a> {
+        let solver = ODESolver::new(self.method);
+        if self.positive {
+            solver.with_positivity()
+        } else {
+            solver
+        }
+    }
+
+    pub fn solve<F>(self, f: F, h: f64, n_steps: usize, t: &Array1<f64>, y_0: f64) -> Array1<f64>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        let y = self
+            .ode_solver()
+            .solve(|t, y| array![f(t, y[0])], h, n_steps, t, &array![y_0]);
+        y.column(0).to_owned()
+    }
+
+    pub fn solve_adaptive<F>(
+        self,
+        f: F,
+        t_span: (f64, f64),
+        y_0: f64,
+        options: &AdaptiveOptions,
+    ) -> Result<(Array1<f64>, Array1<f64>), StepSizeUnderflow>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        let (t, y) = self.ode_solver().solve_adaptive(
+            |t, y| array![f(t, y[0])],
+            t_span,
+            &array![y_0],
+            options,
+        )?;
+        Ok((t, y.column(0).to_owned()))
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct ODESolver2<'a> {
+    method: SolverMethod<'a>,
+    positive: bool,
+}
+
+impl<'a> ODESolver2<'a> {
+    pub fn new(method: SolverMethod<'a>) -> Self {
+        method.check_order();
+        Self {
+            method,
+            positive: false,
+        }
+    }
+
+    /// See `ODESolver::with_positivity`.
+    pub fn with_positivity(mut self) -> Self {
+        self.positive = true;
+        self
+    }
+
+    fn ode_solver(self) -> ODESolver<'a> {
+        let solver = ODESolver::new(self.method);
+        if self.positive {
+            solver.with_positivity()
+        } else {
+            solver
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve<F, G>(
+        self,
+        f1: F,
+        f2: G,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y1_0: f64,
+        y2_0: f64,
+    ) -> (Array1<f64>, Array1<f64>)
+    where
+        F: Fn(f64, f64, f64) -> f64,
+        G: Fn(f64, f64, f64) -> f64,
+    {
+        self.solve_coupled(
+            |t, y1, y2| (f1(t, y1, y2), f2(t, y1, y2)),
+            h,
+            n_steps,
+            t,
+            y1_0,
+            y2_0,
+        )
+    }
+
+    /// `solve` with both derivatives from one call `f(t, y1, y2) = (y1', y2')`, for models that
+    /// compute them together.
+    pub fn solve_coupled<F>(
+        self,
+        f: F,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y1_0: f64,
+        y2_0: f64,
+    ) -> (Array1<f64>, Array1<f64>)
+    where
+        F: Fn(f64, f64, f64) -> (f64, f64),
+    {
+        let y = self.ode_solver().solve(
+            |t, y| {
+                let (f1, f2) = f(t, y[0], y[1]);
+                array![f1, f2]
+            },
+            h,
+            n_steps,
+            t,
+            &array![y1_0, y2_0],
+        );
+        (y.column(0).to_owned(), y.column(1).to_owned())
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn solve_adaptive<F, G>(
+        self,
+        f1: F,
+        f2: G,
+        t_span: (f64, f64),
+        y1_0: f64,
+        y2_0: f64,
+        options: &AdaptiveOptions,
+    ) -> Result<(Array1<f64>, Array1<f64>, Array1<f64>), StepSizeUnderflow>
+    where
+        F: Fn(f64, f64, f64) -> f64,
+        G: Fn(f64, f64, f64) -> f64,
+    {
+        let (t, y) = self.ode_solver().solve_adaptive(
+            |t, y| array![f1(t, y[0], y[1]), f2(t, y[0], y[1])],
+            t_span,
+            &array![y1_0, y2_0],
+            options,
+        )?;
+        Ok((t, y.column(0).to_owned(), y.column(1).to_owned()))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,168 @@
+use super::SolverMethod;
+use ndarray::Array1;
+use std::collections::VecDeque;
+
+/// `y_{n+1} = sum_j alpha_j y_{n-j} + h * (beta_next * f_{n+1} + sum_j beta_j f_{n-j})`.
+/// Explicit methods have `beta_next == 0`.
+#[derive(Copy, Clone, Debug)]
+pub struct LinearMultistep {
+    pub name: &'static str,
+    pub alpha: &'static [f64],
+    pub beta_next: f64,
+    pub beta: &'static [f64],
+    pub order: usize,
+}
+
+impl LinearMultistep {
+    pub fn steps(&self) -> usize {
+        self.alpha.len().max(self.beta.len())
+    }
+
+    /// Applies the formula to the newest-first histories, taking `f_next` as `f_{n+1}`.
+    pub fn apply(
+        &self,
+        h: f64,
+        y: &VecDeque<Array1<f64>>,
+        f: &VecDeque<Array1<f64>>,
+        f_next: Option<&Array1<f64>>,
+    ) -> Array1<f64> {
+        let mut y_next = Array1::zeros(y[0].len());
+        for (alpha_j, y_j) in self.alpha.iter().zip(y) {
+            if *alpha_j != 0. {
+                y_next.scaled_add(*alpha_j, y_j);
+            }
+        }
+        for (beta_j, f_j) in self.beta.iter().zip(f) {
+            y_next.scaled_add(h * beta_j, f_j);
+        }
+        if let Some(f_next) = f_next {
+            y_next.scaled_add(h * self.beta_next, f_next);
+        }
+        y_next
+    }
+}
+
+const fn adams_bashforth(
+    name: &'static str,
+    beta: &'static [f64],
+    order: usize,
+) -> LinearMultistep {
+    LinearMultistep {
+        name,
+        alpha: &[1.],
+        beta_next: 0.,
+        beta,
+        order,
+    }
+}
+
+const fn adams_moulton(
+    name: &'static str,
+    beta_next: f64,
+    beta: &'static [f64],
+    order: usize,
+) -> LinearMultistep {
+    LinearMultistep {
+        name,
+        alpha: &[1.],
+        beta_next,
+        beta,
+        order,
+    }
+}
+
+pub const ADAMS_BASHFORTH: [LinearMultistep; 4] = [
+    adams_bashforth("Adams-Bashforth 2", &[3. / 2., -1. / 2.], 2),
+    adams_bashforth("Adams-Bashforth 3", &[23. / 12., -16. / 12., 5. / 12.], 3),
+    adams_bashforth(
+        "Adams-Bashforth 4",
+        &[55. / 24., -59. / 24., 37. / 24., -9. / 24.],
+        4,
+    ),
+    adams_bashforth(
+        "Adams-Bashforth 5",
+        &[
+            1901. / 720.,
+            -2774. / 720.,
+            2616. / 720.,
+            -1274. / 720.,
+            251. / 720.,
+        ],
+        5,
+    ),
+];
+
+pub const ADAMS_MOULTON: [LinearMultistep; 4] = [
+    adams_moulton("Adams-Moulton 2", 1. / 2., &[1. / 2.], 2),
+    adams_moulton("Adams-Moulton 3", 5. / 12., &[8. / 12., -1. / 12.], 3),
+    adams_moulton(
+        "Adams-Moulton 4",
+        9. / 24.,
+        &[19. / 24., -5. / 24., 1. / 24.],
+        4,
+    ),
+    adams_moulton(
+        "Adams-Moulton 5",
+        251. / 720.,
+        &[646. / 720., -264. / 720., 106. / 720., -19. / 720.],
+        5,
+    ),
+];
+
+pub const NYSTROM2: LinearMultistep = LinearMultistep {
+    name: "Nystrom 2",
+    alpha: &[0., 1.],
+    beta_next: 0.,
+    beta: &[2.],
+    order: 2,
+};
+
+pub const NYSTROM3: LinearMultistep = LinearMultistep {
+    name: "Nystrom 3",
+    alpha: &[0., 1.],
+    beta_next: 0.,
+    beta: &[7. / 3., -2. / 3., 1. / 3.],
+    order: 3,
+};
+
+pub const MILNE4: LinearMultistep = LinearMultistep {
+    name: "Milne 4",
+    alpha: &[0., 0., 0., 1.],
+    beta_next: 0.,
+    beta: &[8. / 3., -4. / 3., 8. / 3.],
+    order: 4,
+};
+
+pub const MILNE_SIMPSON4: LinearMultistep = LinearMultistep {
+    name: "Milne-Simpson 4",
+    alpha: &[0., 1.],
+    beta_next: 1. / 3.,
+    beta: &[4. / 3., 1. / 3.],
+    order: 4,
+};
+
+impl SolverMethod<'_> {
+    /// Predictor and optional PECE corrector, or `None` for the one-step methods.
+    pub fn multistep(self) -> Option<(LinearMultistep, Option<LinearMultistep>)> {
+        let order_index = |order: usize| {
+            assert!(
+                (2..=5).contains(&order),
+                "Adams methods are provided for orders 2 to 5"
+            );
+            order - 2
+        };
+        match self {
+            SolverMethod::AdamsBashforth(order) => {
+                Some((ADAMS_BASHFORTH[order_index(order)], None))
+            }
+            SolverMethod::AdamsMoulton(order) => Some((
+                ADAMS_BASHFORTH[order_index(order)],
+                Some(ADAMS_MOULTON[order_index(order)]),
+            )),
+            SolverMethod::Nystrom2 => Some((NYSTROM2, None)),
+            SolverMethod::Nystrom3 => Some((NYSTROM3, None)),
+            SolverMethod::MilneSimpson4 => Some((MILNE4, Some(MILNE_SIMPSON4))),
+            _ => None,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use ndarray::{Array1, Array2, Zip};
+
+/// Whether a step from `y` to `y_next` takes a positive component below zero. Components already
+/// at zero are not counted, no smaller step keeps them out of the negative range.
+pub(super) fn goes_negative(y: &Array1<f64>, y_next: &Array1<f64>) -> bool {
+    y.iter()
+        .zip(y_next)
+        .any(|(y_i, y_next_i)| *y_i > 0. && *y_next_i < 0.)
+}
+
+/// Sets the components that were non-negative in `y` but are negative in `y_next` to zero.
+pub(super) fn clamp_negative(y: &Array1<f64>, mut y_next: Array1<f64>) -> Array1<f64> {
+    Zip::from(&mut y_next).and(y).for_each(|y_next_i, y_i| {
+        if *y_i >= 0. && *y_next_i < 0. {
+            *y_next_i = 0.;
+        }
+    });
+    y_next
+}
+
+#[derive(Copy, Clone)]
+pub enum PatankarMethod {
+    PatankarEuler,
+    /// Second order modified Patankar-Runge-Kutta scheme built on Heun's method.
+    MPRK22,
+}
+
+impl PatankarMethod {
+    pub fn order(self) -> usize {
+        match self {
+            PatankarMethod::PatankarEuler => 1,
+            PatankarMethod::MPRK22 => 2,
+        }
+    }
+}
+
+/// Integrates `y' = p(t, y) - d(t, y)` split into non-negative production `p` and destruction
+/// `d`. The destruction terms are weighted by `y_next / y` so each step is a positive division
+/// rather than a subtraction, keeping non-negative states non-negative for every step size.
+#[derive(Copy, Clone)]
+pub struct PatankarSolver {
+    method: PatankarMethod,
+}
+
+impl PatankarSolver {
+    pub fn new(method: PatankarMethod) -> Self {
+        Self { method }
+    }
+
+    pub fn solve<P, D>(
+        self,
+        p: P,
+        d: D,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+    ) -> Array2<f64>
+    where
+        P: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        D: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let mut y = Array2::zeros((n_steps + 1, y_0.len()));
+        y.row_mut(0).assign(y_0);
+        for i in 0..n_steps {
+            let y_i = y.row(i).to_owned();
+            let (p_i, d_i) = (p(t[i], &y_i), d(t[i], &y_i));
+            let y_euler = patankar_update(&y_i, &(h * &p_i), &(h * &d_i), &y_i);
+            let y_next = match self.method {
+                PatankarMethod::PatankarEuler => y_euler,
+                PatankarMethod::MPRK22 => {
+                    let p_mean = h / 2. * (p_i + p(t[i] + h, &y_euler));
+                    let d_mean = h / 2. * (d_i + d(t[i] + h, &y_euler));
+                    patankar_update(&y_i, &p_mean, &d_mean, &y_euler)
+                }
+            };
+            y.row_mut(i + 1).assign(&y_next);
+        }
+        y
+    }
+}
+
+/// Solves `y_next = y + production - destruction * y_next / weight` componentwise. A component
+/// with destruction but zero weight is set to zero, the limit of the update as the weight goes
+/// to zero; one without destruction just adds its production.
+fn patankar_update(
+    y: &Array1<f64>,
+    production: &Array1<f64>,
+    destruction: &Array1<f64>,
+    weight: &Array1<f64>,
+) -> Array1<f64> {
+    Array1::from_shape_fn(y.len(), |i| {
+        let numerator = y[i] + production[i];
+        if destruction[i] == 0. {
+            numerator
+        } else if weight[i] > 0. {
+            numerator / (1. + destruction[i] / weight[i])
+        } else {
+            0.
+        }
+    })
+}
\ No newline at end of file
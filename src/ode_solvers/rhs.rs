@@ -0,0 +1,91 @@
+use super::{ODESolver, ODESolver2, Sensitivities};
+use crate::dual::{self, Dual, Real};
+use ndarray::{array, Array1, Array2};
+
+/// A model `y' = f(t, y, p)` written once over any `Real`, so its Jacobians with respect to the
+/// state and the parameters are exact.
+pub trait RightHandSide {
+    /// Names and values of the parameters `p` that `eval` receives, in order.
+    fn parameters(&self) -> Vec<(&'static str, f64)>;
+
+    fn eval<T: Real>(&self, t: f64, y: &Array1<T>, p: &Array1<T>) -> Array1<T>;
+
+    fn parameter_values(&self) -> Array1<f64> {
+        self.parameters().iter().map(|(_, value)| *value).collect()
+    }
+
+    fn f(&self, t: f64, y: &Array1<f64>) -> Array1<f64> {
+        self.eval(t, y, &self.parameter_values())
+    }
+
+    /// `df/dy` at the model's own parameter values.
+    fn jacobian(&self, t: f64, y: &Array1<f64>) -> Array2<f64> {
+        let p = self.parameter_values().mapv(Dual::constant);
+        dual::jacobian(|y| self.eval(t, y, &p), y)
+    }
+}
+
+impl ODESolver<'_> {
+    /// `solve_with_jacobian` with the model's exact Jacobian.
+    pub fn solve_rhs<R: RightHandSide>(
+        self,
+        rhs: &R,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+    ) -> Array2<f64> {
+        self.solve_with_jacobian(
+            |t, y: &Array1<f64>| rhs.f(t, y),
+            |t, y: &Array1<f64>| rhs.jacobian(t, y),
+            h,
+            n_steps,
+            t,
+            y_0,
+        )
+    }
+
+    /// Sensitivities with respect to every parameter of the model, with exact Jacobians.
+    pub fn solve_rhs_sensitivities<R: RightHandSide>(
+        self,
+        rhs: &R,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+    ) -> Sensitivities {
+        self.solve_sensitivities_with_jacobians(
+            |t, y: &Array1<f64>, p: &Array1<f64>| rhs.eval(t, y, p),
+            |t, y: &Array1<f64>, p: &Array1<f64>| {
+                let p = p.mapv(Dual::constant);
+                dual::jacobian(|y| rhs.eval(t, y, &p), y)
+            },
+            |t, y: &Array1<f64>, p: &Array1<f64>| {
+                let y = y.mapv(Dual::constant);
+                dual::jacobian(|p| rhs.eval(t, &y, p), p)
+            },
+            &rhs.parameters(),
+            h,
+            n_steps,
+            t,
+            y_0,
+        )
+    }
+}
+
+impl ODESolver2<'_> {
+    pub fn solve_rhs<R: RightHandSide>(
+        self,
+        rhs: &R,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y1_0: f64,
+        y2_0: f64,
+    ) -> (Array1<f64>, Array1<f64>) {
+        let y = self
+            .ode_solver()
+            .solve_rhs(rhs, h, n_steps, t, &array![y1_0, y2_0]);
+        (y.column(0).to_owned(), y.column(1).to_owned())
+    }
+}
\ No newline at end of file
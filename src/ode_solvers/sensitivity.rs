@@ -0,0 +1,143 @@
+use super::ODESolver;
+use crate::linalg;
+use ndarray::{s, Array1, Array2};
+
+/// A trajectory with its derivatives `dy/dp` with respect to each named parameter, one row per
+/// time point.
+pub struct Sensitivities {
+    pub names: Vec<&'static str>,
+    pub y: Array2<f64>,
+    pub dy_dp: Vec<Array2<f64>>,
+}
+
+impl Sensitivities {
+    pub fn with_respect_to(&self, name: &str) -> &Array2<f64> {
+        let k = self
+            .names
+            .iter()
+            .position(|n| *n == name)
+            .unwrap_or_else(|| panic!("no sensitivity for parameter {}", name));
+        &self.dy_dp[k]
+    }
+}
+
+impl ODESolver<'_> {
+    /// Integrates `y' = f(t, y, p)` together with the forward sensitivity equations
+    /// `s_k' = df/dy s_k + df/dp_k`, `s_k(t_0) = 0`, for `s_k = dy/dp_k`, with the Jacobians taken
+    /// by finite differences. `parameters` gives the name and value of each entry of `p`.
+    pub fn solve_sensitivities<F>(
+        self,
+        f: F,
+        parameters: &[(&'static str, f64)],
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+    ) -> Sensitivities
+    where
+        F: Fn(f64, &Array1<f64>, &Array1<f64>) -> Array1<f64>,
+    {
+        let jac_y = |t: f64, y: &Array1<f64>, p: &Array1<f64>| linalg::jacobian(|y| f(t, y, p), y);
+        let jac_p = |t: f64, y: &Array1<f64>, p: &Array1<f64>| linalg::jacobian(|p| f(t, y, p), p);
+        self.solve_sensitivities_with_jacobians(&f, jac_y, jac_p, parameters, h, n_steps, t, y_0)
+    }
+
+    /// `solve_sensitivities` with the Jacobians `df/dy` and `df/dp` supplied, e.g. analytically or
+    /// by dual numbers.
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve_sensitivities_with_jacobians<F, JY, JP>(
+        self,
+        f: F,
+        jac_y: JY,
+        jac_p: JP,
+        parameters: &[(&'static str, f64)],
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        y_0: &Array1<f64>,
+    ) -> Sensitivities
+    where
+        F: Fn(f64, &Array1<f64>, &Array1<f64>) -> Array1<f64>,
+        JY: Fn(f64, &Array1<f64>, &Array1<f64>) -> Array2<f64>,
+        JP: Fn(f64, &Array1<f64>, &Array1<f64>) -> Array2<f64>,
+    {
+        let (n, m) = (y_0.len(), parameters.len());
+        let p: Array1<f64> = parameters.iter().map(|(_, value)| *value).collect();
+
+        // z = [y, s_1, ..., s_m]
+        let augmented = |t: f64, z: &Array1<f64>| {
+            let y = z.slice(s![..n]).to_owned();
+            let sensitivities = z.slice(s![n..]).into_shape((m, n)).unwrap().reversed_axes();
+            let ds = jac_y(t, &y, &p).dot(&sensitivities) + jac_p(t, &y, &p);
+
+            let mut dz = Array1::zeros(z.len());
+            dz.slice_mut(s![..n]).assign(&f(t, &y, &p));
+            for k in 0..m {
+                dz.slice_mut(s![n * (k + 1)..n * (k + 2)])
+                    .assign(&ds.column(k));
+            }
+            dz
+        };
+        let mut z_0 = Array1::zeros(n * (m + 1));
+        z_0.slice_mut(s![..n]).assign(y_0);
+        let z = self.solve(augmented, h, n_steps, t, &z_0);
+
+        Sensitivities {
+            names: parameters.iter().map(|(name, _)| *name).collect(),
+            y: z.slice(s![.., ..n]).to_owned(),
+            dy_dp: (0..m)
+                .map(|k| z.slice(s![.., n * (k + 1)..n * (k + 2)]).to_owned())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ode_solvers::SolverMethod;
+    use ndarray::array;
+
+    #[test]
+    fn decay_rate_sensitivity_matches_the_exact_derivative() {
+        // y = e^(-k t), so dy/dk = -t e^(-k t)
+        let (k, h, n_steps) = (0.5, 0.01, 400);
+        let t = Array1::linspace(0., 4., n_steps + 1);
+        let sensitivities = ODESolver::new(SolverMethod::Classic4).solve_sensitivities(
+            |_, y, p| -p[0] * y,
+            &[("k", k)],
+            h,
+            n_steps,
+            &t,
+            &array![1.],
+        );
+        let dy_dk = sensitivities.with_respect_to("k").column(0).to_owned();
+        let exact = t.mapv(|t| -t * (-k * t).exp());
+        assert!((dy_dk - exact).iter().all(|e| e.abs() < 1e-8));
+    }
+
+    #[test]
+    fn logistic_sensitivities_match_finite_differences() {
+        let logistic = |_: f64, y: &Array1<f64>, p: &Array1<f64>| p[0] * y * (1. - y / p[1]);
+        let parameters = [("r", 0.8), ("k", 10.)];
+        let (h, n_steps) = (0.01, 1000);
+        let t = Array1::linspace(0., 10., n_steps + 1);
+        let y_0 = array![1.];
+        let solver = ODESolver::new(SolverMethod::Classic4);
+        let sensitivities = solver.solve_sensitivities(logistic, &parameters, h, n_steps, &t, &y_0);
+
+        let delta = 1e-6;
+        for (k, (name, _)) in parameters.iter().enumerate() {
+            let shifted = |shift: f64| {
+                let mut p: Array1<f64> = parameters.iter().map(|(_, value)| *value).collect();
+                p[k] += shift;
+                solver.solve(|t, y| logistic(t, y, &p), h, n_steps, &t, &y_0)
+            };
+            let difference = (shifted(delta) - shifted(-delta)) / (2. * delta);
+            let error = (&difference - sensitivities.with_respect_to(name))
+                .iter()
+                .fold(0., |e: f64, d| e.max(d.abs()));
+            assert!(error < 1e-6, "{}: {:e}", name, error);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,186 @@
+use super::implicit::{
+    radau_iia_step, rosenbrock2_step, solve_implicit_equation, BDF_COEFFICIENTS,
+};
+use super::multistep::LinearMultistep;
+use super::positivity::{clamp_negative, goes_negative};
+use super::{ButcherTableau, ODESolver, SolverMethod};
+use ndarray::{Array1, Array2};
+use std::collections::VecDeque;
+
+/// How many times `with_positivity` halves a step before clamping at zero.
+const MAX_POSITIVITY_HALVINGS: usize = 10;
+/// How many times an implicit step whose Newton iteration fails is halved before giving up.
+const MAX_NEWTON_HALVINGS: usize = 10;
+
+/// Advances a solution by fixed steps of size `h`, keeping whatever history the method needs.
+pub struct Stepper<'a, F, J> {
+    method: SolverMethod<'a>,
+    starter: ButcherTableau<'a>,
+    f: &'a F,
+    jac: &'a J,
+    h: f64,
+    positive: bool,
+    multistep: Option<(LinearMultistep, Option<LinearMultistep>)>,
+    history_len: usize,
+    steps_taken: usize,
+    y_history: VecDeque<Array1<f64>>,
+    f_history: VecDeque<Array1<f64>>,
+}
+
+impl<'a, F, J> Stepper<'a, F, J>
+where
+    F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    J: Fn(f64, &Array1<f64>) -> Array2<f64>,
+{
+    pub fn new(
+        solver: ODESolver<'a>,
+        f: &'a F,
+        jac: &'a J,
+        h: f64,
+        t_0: f64,
+        y_0: &Array1<f64>,
+    ) -> Self {
+        if let Some(tableau) = solver.method.tableau() {
+            debug_assert!(
+                tableau.satisfies_order_conditions(1e-12),
+                "{} does not satisfy its order conditions",
+                tableau.name
+            );
+        }
+        let multistep = solver.method.multistep();
+        let history_len = match (solver.method, multistep) {
+            (_, Some((predictor, corrector))) => {
+                corrector.map_or(predictor.steps(), |c| c.steps().max(predictor.steps()))
+            }
+            (SolverMethod::BDF(order), None) => order,
+            _ => 1,
+        };
+        let f_history = match multistep {
+            Some(_) => VecDeque::from([f(t_0, y_0)]),
+            None => VecDeque::new(),
+        };
+        Self {
+            method: solver.method,
+            starter: solver.starter,
+            f,
+            jac,
+            h,
+            positive: solver.positive,
+            multistep,
+            history_len,
+            steps_taken: 0,
+            y_history: VecDeque::from([y_0.clone()]),
+            f_history,
+        }
+    }
+
+    pub fn y(&self) -> &Array1<f64> {
+        &self.y_history[0]
+    }
+
+    /// Takes one step from `(t, self.y())` and returns the new state.
+    pub fn step(&mut self, t: f64) -> Array1<f64> {
+        let (f, jac, h) = (self.f, self.jac, self.h);
+        let y_n = &self.y_history[0];
+        let y_next = match (self.method, self.multistep) {
+            (_, Some(_)) | (SolverMethod::BDF(_), _) if self.steps_taken + 1 < self.history_len => {
+                // not enough history yet, start with a one-step method
+                match self.method {
+                    SolverMethod::BDF(_) => self.one_step(t, y_n, h),
+                    _ => self.starter.step(f, t, y_n, h),
+                }
+            }
+            (_, Some((predictor, corrector))) => {
+                let y_predicted = predictor.apply(h, &self.y_history, &self.f_history, None);
+                match corrector {
+                    Some(corrector) => {
+                        let f_predicted = f(t + h, &y_predicted);
+                        corrector.apply(h, &self.y_history, &self.f_history, Some(&f_predicted))
+                    }
+                    None => y_predicted,
+                }
+            }
+            (SolverMethod::BDF(order), None) => {
+                let (alpha, beta) = BDF_COEFFICIENTS[order - 1];
+                let mut psi = Array1::zeros(y_n.len());
+                for (alpha_j, y_j) in alpha.iter().zip(&self.y_history) {
+                    psi.scaled_add(*alpha_j, y_j);
+                }
+                // a failed Newton iteration is retried as cut Radau IIA steps, like the startup
+                solve_implicit_equation(f, jac, t + h, &psi, beta * h, y_n.clone())
+                    .unwrap_or_else(|| self.one_step(t, y_n, h))
+            }
+            _ => self.one_step(t, y_n, h),
+        };
+        let y_next = if !self.positive {
+            y_next
+        } else if goes_negative(y_n, &y_next) {
+            let y_half = self.positive_step(t, y_n, h / 2., 1);
+            self.positive_step(t + h / 2., &y_half, h / 2., 1)
+        } else {
+            clamp_negative(y_n, y_next)
+        };
+
+        if self.multistep.is_some() {
+            self.f_history.push_front(f(t + h, &y_next));
+            self.f_history.truncate(self.history_len);
+        }
+        self.y_history.push_front(y_next.clone());
+        self.y_history.truncate(self.history_len);
+        self.steps_taken += 1;
+        y_next
+    }
+
+    /// A single step of arbitrary size from `(t, y)` that leaves the history untouched.
+    /// Multistep methods fall back to the starter. An implicit step whose Newton iteration fails is
+    /// split in halves, and it panics if that still fails after `MAX_NEWTON_HALVINGS` cuts.
+    pub fn one_step(&self, t: f64, y: &Array1<f64>, h: f64) -> Array1<f64> {
+        self.cut_step(t, y, h, 0)
+    }
+
+    fn cut_step(&self, t: f64, y: &Array1<f64>, h: f64, halvings: usize) -> Array1<f64> {
+        if let Some(y_next) = self.try_one_step(t, y, h) {
+            return y_next;
+        }
+        assert!(
+            halvings < MAX_NEWTON_HALVINGS,
+            "the Newton iteration fails at t = {} even with steps of {:e}",
+            t,
+            h
+        );
+        let y_half = self.cut_step(t, y, h / 2., halvings + 1);
+        self.cut_step(t + h / 2., &y_half, h / 2., halvings + 1)
+    }
+
+    /// `one_step` without cutting, `None` where an implicit method's Newton iteration fails.
+    fn try_one_step(&self, t: f64, y: &Array1<f64>, h: f64) -> Option<Array1<f64>> {
+        let (f, jac) = (self.f, self.jac);
+        match self.method {
+            SolverMethod::BackwardEuler => solve_implicit_equation(f, jac, t + h, y, h, y.clone()),
+            SolverMethod::Trapezoidal => {
+                let psi = y + &(h / 2. * f(t, y));
+                solve_implicit_equation(f, jac, t + h, &psi, h / 2., y.clone())
+            }
+            SolverMethod::ImplicitMidpoint => {
+                // solve for the midpoint z = y + h/2 f(t + h/2, z), then y_next = 2z - y
+                let z = solve_implicit_equation(f, jac, t + h / 2., y, h / 2., y.clone())?;
+                Some(2. * z - y)
+            }
+            SolverMethod::RadauIIA3 => radau_iia_step(f, jac, t, y, h, 2),
+            SolverMethod::RadauIIA5 | SolverMethod::BDF(_) => radau_iia_step(f, jac, t, y, h, 3),
+            SolverMethod::Rosenbrock2 => rosenbrock2_step(f, jac, t, y, h),
+            method => Some(method.tableau().unwrap_or(self.starter).step(f, t, y, h)),
+        }
+    }
+
+    /// `one_step`, split in halves while it would take a component below zero.
+    fn positive_step(&self, t: f64, y: &Array1<f64>, h: f64, halvings: usize) -> Array1<f64> {
+        let y_next = self.one_step(t, y, h);
+        if !goes_negative(y, &y_next) || halvings == MAX_POSITIVITY_HALVINGS {
+            clamp_negative(y, y_next)
+        } else {
+            let y_half = self.positive_step(t, y, h / 2., halvings + 1);
+            self.positive_step(t + h / 2., &y_half, h / 2., halvings + 1)
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,153 @@
+use ndarray::{array, Array1, Array2};
+
+#[derive(Copy, Clone)]
+pub enum SymplecticMethod {
+    SymplecticEuler,
+    StormerVerlet,
+}
+
+impl SymplecticMethod {
+    pub fn order(self) -> usize {
+        match self {
+            SymplecticMethod::SymplecticEuler => 1,
+            SymplecticMethod::StormerVerlet => 2,
+        }
+    }
+}
+
+/// Explicit symplectic integrators for separable partitioned systems `q' = f(t, p)`,
+/// `p' = g(t, q)`, such as Hamiltonians `H = T(p) + V(q)`.
+#[derive(Copy, Clone)]
+pub struct PartitionedSolver {
+    method: SymplecticMethod,
+}
+
+impl PartitionedSolver {
+    pub fn new(method: SymplecticMethod) -> Self {
+        Self { method }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve<F, G>(
+        self,
+        f: F,
+        g: G,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        q_0: &Array1<f64>,
+        p_0: &Array1<f64>,
+    ) -> (Array2<f64>, Array2<f64>)
+    where
+        F: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        G: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let mut q = Array2::zeros((n_steps + 1, q_0.len()));
+        let mut p = Array2::zeros((n_steps + 1, p_0.len()));
+        q.row_mut(0).assign(q_0);
+        p.row_mut(0).assign(p_0);
+        for i in 0..n_steps {
+            let (q_i, p_i) = (q.row(i).to_owned(), p.row(i).to_owned());
+            let (q_next, p_next) = match self.method {
+                SymplecticMethod::SymplecticEuler => {
+                    let q_next = q_i + h * f(t[i], &p_i);
+                    let p_next = p_i + h * g(t[i] + h, &q_next);
+                    (q_next, p_next)
+                }
+                SymplecticMethod::StormerVerlet => {
+                    let q_half = q_i + h / 2. * f(t[i], &p_i);
+                    let p_next = p_i + h * g(t[i] + h / 2., &q_half);
+                    let q_next = q_half + h / 2. * f(t[i] + h, &p_next);
+                    (q_next, p_next)
+                }
+            };
+            q.row_mut(i + 1).assign(&q_next);
+            p.row_mut(i + 1).assign(&p_next);
+        }
+        (q, p)
+    }
+
+    /// Poisson integrator for `n' = alpha1 n - beta1 n p`, `p' = -alpha2 p + beta2 n p`. In
+    /// `u = ln n`, `v = ln p` the system is separable with Hamiltonian
+    /// `beta2 e^u - alpha2 u + beta1 e^v - alpha1 v`, so the symplectic methods keep the first
+    /// integral bounded and both populations positive.
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve_lotka_volterra(
+        self,
+        (alpha1, beta1, alpha2, beta2): (f64, f64, f64, f64),
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        n_0: f64,
+        p_0: f64,
+    ) -> (Array1<f64>, Array1<f64>) {
+        let du_by_dt = |_: f64, v: &Array1<f64>| array![alpha1 - beta1 * v[0].exp()];
+        let dv_by_dt = |_: f64, u: &Array1<f64>| array![-alpha2 + beta2 * u[0].exp()];
+        let (u, v) = self.solve(
+            du_by_dt,
+            dv_by_dt,
+            h,
+            n_steps,
+            t,
+            &array![n_0.ln()],
+            &array![p_0.ln()],
+        );
+        (u.column(0).mapv(f64::exp), v.column(0).mapv(f64::exp))
+    }
+}
+
+pub fn lotka_volterra_first_integral(
+    (alpha1, beta1, alpha2, beta2): (f64, f64, f64, f64),
+    n: f64,
+    p: f64,
+) -> f64 {
+    beta2 * n - alpha2 * n.ln() + beta1 * p - alpha1 * p.ln()
+}
+
+/// Relative change `|I(y_i) - I(y_0)| / |I(y_0)|` of a first integral along a trajectory with
+/// one row per time point, or the absolute change `|I(y_i) - I(y_0)|` when `I(y_0)` is zero.
+pub fn first_integral_drift<I>(first_integral: I, y: &Array2<f64>) -> Array1<f64>
+where
+    I: Fn(&Array1<f64>) -> f64,
+{
+    let i_0 = first_integral(&y.row(0).to_owned());
+    let scale = if i_0 == 0. { 1. } else { i_0.abs() };
+    y.rows()
+        .into_iter()
+        .map(|y_i| (first_integral(&y_i.to_owned()) - i_0).abs() / scale)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift_is_relative_unless_the_first_integral_starts_at_zero() {
+        let y = array![[1.], [1.5], [0.5]];
+        let relative = first_integral_drift(|y| y[0], &y);
+        assert_eq!(relative, array![0., 0.5, 0.5]);
+        let absolute = first_integral_drift(|y| y[0] - 1., &y);
+        assert_eq!(absolute, array![0., 0.5, 0.5]);
+    }
+
+    #[test]
+    fn stormer_verlet_keeps_the_oscillator_energy_bounded() {
+        // q' = p, p' = -q, whose energy (q^2 + p^2) / 2 stays within about h^2 / 4 of its start
+        let (h, n_steps) = (0.1, 10_000);
+        let t = Array1::linspace(0., h * n_steps as f64, n_steps + 1);
+        let (q, p) = PartitionedSolver::new(SymplecticMethod::StormerVerlet).solve(
+            |_, p| p.clone(),
+            |_, q| -q,
+            h,
+            n_steps,
+            &t,
+            &array![1.],
+            &array![0.],
+        );
+        let y = ndarray::concatenate![ndarray::Axis(1), q, p];
+        let drift = first_integral_drift(|y| 0.5 * y.dot(y), &y);
+        let max_drift = drift.fold(0., |m: f64, d| m.max(*d));
+        assert!(max_drift < 1.01 * h * h / 4., "{}", max_drift);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,257 @@
+use ndarray::{Array1, Array2, Axis};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum Interpretation {
+    Ito,
+    Stratonovich,
+}
+
+#[derive(Copy, Clone)]
+pub enum SDEMethod {
+    EulerMaruyama,
+    Milstein,
+    /// Platen's derivative-free Runge-Kutta scheme of Milstein type.
+    SRK,
+}
+
+impl SDEMethod {
+    pub fn strong_order(self) -> f64 {
+        match self {
+            SDEMethod::EulerMaruyama => 0.5,
+            SDEMethod::Milstein | SDEMethod::SRK => 1.,
+        }
+    }
+
+    /// The schemes only differ in strong order: each matches the moments to first order.
+    pub fn weak_order(self) -> f64 {
+        match self {
+            SDEMethod::EulerMaruyama | SDEMethod::Milstein | SDEMethod::SRK => 1.,
+        }
+    }
+}
+
+/// Mean and pointwise quantiles over an ensemble of sample paths, one row per time point.
+pub struct EnsembleStatistics {
+    pub mean: Array2<f64>,
+    pub levels: Vec<f64>,
+    pub quantiles: Vec<Array2<f64>>,
+}
+
+impl EnsembleStatistics {
+    /// `paths[p]` is sample path `p` with one row per time point.
+    pub fn from_paths(paths: &[Array2<f64>], levels: &[f64]) -> Self {
+        let views: Vec<_> = paths.iter().map(|p| p.view()).collect();
+        let stacked = ndarray::stack(Axis(0), &views).unwrap();
+        let mean = stacked.mean_axis(Axis(0)).unwrap();
+
+        let (n_times, dim) = (stacked.shape()[1], stacked.shape()[2]);
+        let mut quantiles = vec![Array2::zeros((n_times, dim)); levels.len()];
+        for i in 0..n_times {
+            for j in 0..dim {
+                let mut samples: Vec<f64> = stacked.slice(ndarray::s![.., i, j]).to_vec();
+                samples.sort_by(f64::total_cmp);
+                for (q, level) in quantiles.iter_mut().zip(levels) {
+                    q[[i, j]] = quantile_of_sorted(&samples, *level);
+                }
+            }
+        }
+        Self {
+            mean,
+            levels: levels.to_vec(),
+            quantiles,
+        }
+    }
+
+    pub fn quantile(&self, level: f64) -> &Array2<f64> {
+        let i = self
+            .levels
+            .iter()
+            .position(|l| (l - level).abs() < 1e-12)
+            .unwrap_or_else(|| panic!("quantile {} was not computed", level));
+        &self.quantiles[i]
+    }
+}
+
+/// Linearly interpolated quantile of `samples`, which must already be sorted in ascending order.
+/// NaN when there are no samples.
+pub fn quantile_of_sorted(samples: &[f64], level: f64) -> f64 {
+    if samples.is_empty() {
+        return f64::NAN;
+    }
+    let pos = level.clamp(0., 1.) * (samples.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    samples[lo] + (pos - lo as f64) * (samples[hi] - samples[lo])
+}
+
+/// Solves `dX = a(t, X) dt + b(t, X) dW` with diagonal noise: component `i` is driven by its own
+/// Wiener process with intensity `b_i`. Additive noise has `b` independent of `X`,
+/// multiplicative noise e.g. `b = sigma * X`.
+#[derive(Copy, Clone)]
+pub struct SDESolver {
+    method: SDEMethod,
+    interpretation: Interpretation,
+    seed: u64,
+}
+
+impl SDESolver {
+    pub fn new(method: SDEMethod, interpretation: Interpretation, seed: u64) -> Self {
+        Self {
+            method,
+            interpretation,
+            seed,
+        }
+    }
+
+    /// A single sample path seeded with the solver's seed.
+    pub fn solve<A, B>(
+        self,
+        a: A,
+        b: B,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        x_0: &Array1<f64>,
+    ) -> Array2<f64>
+    where
+        A: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        B: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        self.solve_with_rng(&a, &b, h, n_steps, t, x_0, &mut rng)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn solve_with_rng<A, B, R>(
+        self,
+        a: &A,
+        b: &B,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        x_0: &Array1<f64>,
+        rng: &mut R,
+    ) -> Array2<f64>
+    where
+        A: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        B: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        R: Rng,
+    {
+        let dim = x_0.len();
+        let mut x = Array2::zeros((n_steps + 1, dim));
+        x.row_mut(0).assign(x_0);
+        for i in 0..n_steps {
+            let x_i = x.row(i).to_owned();
+            let dw: Array1<f64> = (0..dim)
+                .map(|_| h.sqrt() * rng.sample::<f64, _>(StandardNormal))
+                .collect();
+            x.row_mut(i + 1)
+                .assign(&self.step(a, b, t[i], &x_i, h, &dw));
+        }
+        x
+    }
+
+    fn step<A, B>(
+        &self,
+        a: &A,
+        b: &B,
+        t: f64,
+        x: &Array1<f64>,
+        h: f64,
+        dw: &Array1<f64>,
+    ) -> Array1<f64>
+    where
+        A: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        B: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let a_x = a(t, x);
+        let b_x = b(t, x);
+        let ito = self.interpretation == Interpretation::Ito;
+        match self.method {
+            SDEMethod::EulerMaruyama => {
+                // Stratonovich drift carries the extra 1/2 b db/dx of the Ito form
+                let drift = if ito {
+                    a_x
+                } else {
+                    a_x + 0.5 * &b_x * &diagonal_derivative(b, t, x)
+                };
+                x + &(drift * h) + &b_x * dw
+            }
+            SDEMethod::Milstein => {
+                let db = diagonal_derivative(b, t, x);
+                let dw2 = if ito { dw * dw - h } else { dw * dw };
+                x + &(a_x * h) + &b_x * dw + 0.5 * &b_x * &db * &dw2
+            }
+            SDEMethod::SRK => {
+                if ito {
+                    let support = x + &(&a_x * h) + &(&b_x * h.sqrt());
+                    let b_support = b(t, &support);
+                    let dw2 = dw * dw - h;
+                    x + &(a_x * h) + &b_x * dw + (b_support - &b_x) * &dw2 / (2. * h.sqrt())
+                } else {
+                    let support = x + &(&a_x * h) + &(&b_x * dw);
+                    let b_support = b(t, &support);
+                    x + &(a_x * h) + 0.5 * (b_x + b_support) * dw
+                }
+            }
+        }
+    }
+
+    /// Runs `n_paths` sample paths from one RNG stream seeded with the solver's seed and returns
+    /// their mean and the requested quantiles, e.g. `&[0.05, 0.5, 0.95]`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn ensemble<A, B>(
+        self,
+        a: A,
+        b: B,
+        h: f64,
+        n_steps: usize,
+        t: &Array1<f64>,
+        x_0: &Array1<f64>,
+        n_paths: usize,
+        levels: &[f64],
+    ) -> EnsembleStatistics
+    where
+        A: Fn(f64, &Array1<f64>) -> Array1<f64>,
+        B: Fn(f64, &Array1<f64>) -> Array1<f64>,
+    {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let paths: Vec<Array2<f64>> = (0..n_paths)
+            .map(|_| self.solve_with_rng(&a, &b, h, n_steps, t, x_0, &mut rng))
+            .collect();
+        EnsembleStatistics::from_paths(&paths, levels)
+    }
+}
+
+/// `db_i/dx_i` by forward differences, all the Milstein correction needs for diagonal noise.
+fn diagonal_derivative<B>(b: &B, t: f64, x: &Array1<f64>) -> Array1<f64>
+where
+    B: Fn(f64, &Array1<f64>) -> Array1<f64>,
+{
+    let b_x = b(t, x);
+    let mut x_h = x.clone();
+    Array1::from_shape_fn(x.len(), |i| {
+        let h = f64::EPSILON.sqrt() * x[i].abs().max(1.);
+        x_h[i] = x[i] + h;
+        let db = (b(t, &x_h)[i] - b_x[i]) / h;
+        x_h[i] = x[i];
+        db
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantiles_interpolate_between_sorted_samples() {
+        let samples = [1., 2., 4., 8.];
+        assert_eq!(quantile_of_sorted(&samples, 0.), 1.);
+        assert_eq!(quantile_of_sorted(&samples, 0.5), 3.);
+        assert_eq!(quantile_of_sorted(&samples, 1.), 8.);
+        assert_eq!(quantile_of_sorted(&[5.], 0.3), 5.);
+        assert!(quantile_of_sorted(&[], 0.5).is_nan());
+    }
+}
\ No newline at end of file